@@ -0,0 +1,231 @@
+//! Approximate equality for floating point values.
+//!
+//! Floats unify with `==`, so values computed along different paths (such as
+//! `0.1 + 0.2` and `0.3`) will usually fail to unify. Wrapping them in
+//! [`Approx`] makes unification and the [comparison goals](crate::cmp)
+//! tolerate small differences.
+//!
+//! ```
+//! use canrun::{all, unify, var, Goal};
+//! use canrun_basic::add;
+//! use canrun_basic::approx::Approx;
+//! use canrun_basic::example::Floats;
+//!
+//! let x = var();
+//! let goal: Goal<Floats> = all![
+//!     add(Approx::new(0.1), Approx::new(0.2), x),
+//!     unify(x, Approx::new(0.3)),
+//! ];
+//! let results: Vec<_> = goal.query(x).collect();
+//! assert_eq!(results, vec![Approx::new(0.3)]);
+//! ```
+
+use canrun::{Domain, DomainType, ReifyIn, ResolvedState, State, UnifyIn};
+use num_traits::Float;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::rc::Rc;
+
+/// A float that is considered equal to any value within a tolerance.
+///
+/// Two values are equal when `|a - b| <= tolerance * max(1, |a|, |b|)`, using
+/// the larger tolerance of the two. This is absolute near zero and relative
+/// for larger magnitudes.
+///
+/// Note that approximate equality is not transitive. Two values that each
+/// unify with a third may not unify with each other.
+#[derive(Clone, Copy, Debug)]
+pub struct Approx<T> {
+    /// The wrapped value.
+    pub value: T,
+    /// How far apart values may be while still considered equal.
+    pub tolerance: T,
+}
+
+impl<T: Float> Approx<T> {
+    /// Wrap a value with the default tolerance, which is the square root of
+    /// the machine epsilon (about `1.5e-8` for `f64`).
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_basic::approx::Approx;
+    ///
+    /// assert_eq!(Approx::new(0.1 + 0.2), Approx::new(0.3));
+    /// assert_ne!(Approx::new(0.1), Approx::new(0.2));
+    /// ```
+    pub fn new(value: T) -> Self {
+        Approx {
+            value,
+            tolerance: T::epsilon().sqrt(),
+        }
+    }
+
+    /// Wrap a value with a specific tolerance.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_basic::approx::Approx;
+    ///
+    /// assert_eq!(Approx::with_tolerance(1.0, 0.1), Approx::new(1.05));
+    /// ```
+    pub fn with_tolerance(value: T, tolerance: T) -> Self {
+        Approx { value, tolerance }
+    }
+
+    fn combine(self, other: Self, value: T) -> Self {
+        Approx {
+            value,
+            tolerance: self.tolerance.max(other.tolerance),
+        }
+    }
+}
+
+impl<T: Float> From<T> for Approx<T> {
+    fn from(value: T) -> Self {
+        Approx::new(value)
+    }
+}
+
+impl<T: Float> PartialEq for Approx<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // The scale is infinite if either value is, which would make every
+        // value equal to infinity.
+        if !self.value.is_finite() || !other.value.is_finite() {
+            return self.value == other.value;
+        }
+        let scale = T::one().max(self.value.abs()).max(other.value.abs());
+        let tolerance = self.tolerance.max(other.tolerance);
+        (self.value - other.value).abs() <= tolerance * scale
+    }
+}
+
+/// Values within the tolerance are ordered as equal, so `lt` and `gt` will
+/// not consider them to be different.
+impl<T: Float> PartialOrd for Approx<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else {
+            self.value.partial_cmp(&other.value)
+        }
+    }
+}
+
+macro_rules! impl_approx_op {
+    ($trait:ident, $fn:ident, $op:tt) => {
+        impl<T: Float> $trait for Approx<T> {
+            type Output = Self;
+            fn $fn(self, other: Self) -> Self {
+                self.combine(other, self.value $op other.value)
+            }
+        }
+    };
+}
+
+impl_approx_op!(Add, add, +);
+impl_approx_op!(Sub, sub, -);
+impl_approx_op!(Mul, mul, *);
+impl_approx_op!(Div, div, /);
+
+impl<T: Float> Neg for Approx<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Approx {
+            value: -self.value,
+            tolerance: self.tolerance,
+        }
+    }
+}
+
+impl<'a, T, D> UnifyIn<'a, D> for Approx<T>
+where
+    T: Float + Debug,
+    D: DomainType<'a, Approx<T>>,
+{
+    fn unify_resolved(state: State<'a, D>, a: Rc<Self>, b: Rc<Self>) -> Option<State<'a, D>> {
+        if a == b {
+            Some(state)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T, D> ReifyIn<'a, D> for Approx<T>
+where
+    T: Float + Debug,
+    D: Domain<'a>,
+{
+    type Reified = Approx<T>;
+    fn reify_in(&self, _: &ResolvedState<D>) -> Option<Self::Reified> {
+        Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Approx;
+    use crate::cmp::bounds::{gt, lte};
+    use crate::example::Floats;
+    use crate::ops::{mul, sub};
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn unifies_computed_values() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Floats>> = vec![
+            unify(x, Approx::new(0.7)),
+            sub(x, Approx::new(0.4), y),
+            unify(y, Approx::new(0.3)),
+        ];
+        util::assert_permutations_resolve_to(goals, y, vec![Approx::new(0.3)]);
+    }
+
+    #[test]
+    fn scales_with_magnitude() {
+        let x = var();
+        let goals: Vec<Goal<Floats>> = vec![
+            mul(Approx::new(1e20), Approx::new(3.0), x),
+            unify(x, Approx::new(3e20 + 1e5)),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![Approx::new(3e20)]);
+    }
+
+    #[test]
+    fn fails_outside_tolerance() {
+        let x = var();
+        let goals: Vec<Goal<Floats>> = vec![
+            unify(x, Approx::with_tolerance(1.0, 0.01)),
+            unify(x, Approx::new(1.1)),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn infinities_are_exact() {
+        let inf = f64::INFINITY;
+        assert_ne!(Approx::new(inf), Approx::new(1.0));
+        assert_ne!(Approx::new(1.0), Approx::new(-inf));
+        assert_ne!(Approx::new(inf), Approx::new(-inf));
+        assert_eq!(Approx::new(inf), Approx::new(inf));
+        assert!(Approx::new(1.0) < Approx::new(inf));
+
+        let x = var();
+        let goals: Vec<Goal<Floats>> =
+            vec![unify(x, Approx::new(inf)), unify(x, Approx::new(f64::MAX))];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn comparisons_ignore_tiny_differences() {
+        let x = var();
+        let goals: Vec<Goal<Floats>> =
+            vec![unify(x, Approx::new(0.1 + 0.2)), lte(x, Approx::new(0.3))];
+        util::assert_permutations_resolve_to(goals, x, vec![Approx::new(0.3)]);
+
+        let goals: Vec<Goal<Floats>> =
+            vec![unify(x, Approx::new(0.1 + 0.2)), gt(x, Approx::new(0.3))];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
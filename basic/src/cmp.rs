@@ -1,16 +1,32 @@
 //! Comparison goals including [`lt`](lt::lt), [`lte`](lte::lte),
 //! [`gt`](gt::gt), [`gte`](gte::gte), [`min`](min::min) and [`max`](max::max).
+//!
+//! [`min_of`](min_of::min_of) and [`max_of`](max_of::max_of) compare every
+//! item in an [`LVec`](canrun_collections::lvec::LVec), and
+//! [`between`](between::between) generates the integers in a range.
+//!
+//! The ordering goals wait for both sides to be resolved. The goals in
+//! [`bounds`] narrow the interval each var may fall in instead, so
+//! contradictions fail before anything is resolved.
 
+mod between;
+pub mod bounds;
+mod extreme;
 mod gt;
 mod gte;
 mod lt;
 mod lte;
 mod max;
+mod max_of;
 mod min;
+mod min_of;
 
+pub use between::between;
 pub use gt::gt;
 pub use gte::gte;
 pub use lt::lt;
 pub use lte::lte;
 pub use max::max;
+pub use max_of::max_of;
 pub use min::min;
+pub use min_of::min_of;
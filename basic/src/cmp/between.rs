@@ -0,0 +1,140 @@
+use crate::cmp::bounds::lte;
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{resolve_2, Constraint, ResolveFn, VarWatch},
+    Fork, State, StateIter,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{all, DomainType, UnifyIn};
+use num_traits::PrimInt;
+use std::fmt::Debug;
+use std::iter::successors;
+use std::rc::Rc;
+
+/// Relate a value to an inclusive range of integers.
+///
+/// If the value is resolved it is simply checked against the range. Otherwise
+/// the state is lazily forked for each integer from `low` to `high` once both
+/// ends are resolved. The ends can be vars that are resolved later, and the
+/// range narrows the [bounds](crate::cmp::bounds) of the value in the meantime.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::between;
+///
+/// let x = var();
+/// let goal: Goal<I32> = between(1, 3, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![1, 2, 3]);
+/// ```
+pub fn between<'a, T, L, H, X, D>(low: L, high: H, x: X) -> Goal<'a, D>
+where
+    T: PrimInt + UnifyIn<'a, D> + Debug + 'static,
+    L: IntoVal<T>,
+    H: IntoVal<T>,
+    X: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    let (low, high, x) = (low.into_val(), high.into_val(), x.into_val());
+    all![
+        lte(low.clone(), x.clone()),
+        lte(x.clone(), high.clone()),
+        Goal::constraint(Between { low, high, x }),
+    ]
+}
+
+#[derive(Debug)]
+struct Between<T: Debug> {
+    low: Val<T>,
+    high: Val<T>,
+    x: Val<T>,
+}
+
+impl<'a, T, D> Constraint<'a, D> for Between<T>
+where
+    T: PrimInt + UnifyIn<'a, D> + Debug + 'static,
+    D: DomainType<'a, T>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let (low, high) = resolve_2(&self.low, &self.high, state)?;
+        let range = Range {
+            low: *low,
+            high: *high,
+            x: self.x.clone(),
+        };
+        Ok(Box::new(|state| state.fork(Rc::new(range))))
+    }
+}
+
+#[derive(Debug)]
+struct Range<T: Debug> {
+    low: T,
+    high: T,
+    x: Val<T>,
+}
+
+impl<'a, T, D> Fork<'a, D> for Range<T>
+where
+    T: PrimInt + UnifyIn<'a, D> + Debug + 'static,
+    D: DomainType<'a, T>,
+{
+    fn fork(&self, state: State<'a, D>) -> StateIter<'a, D> {
+        let (low, high, x) = (self.low, self.high, self.x.clone());
+        if let Val::Resolved(_) = state.resolve_val(&x) {
+            return Box::new(std::iter::once(state));
+        }
+        let first = Some(low).filter(|low| *low <= high);
+        let values = successors(
+            first,
+            move |n| if *n < high { Some(*n + T::one()) } else { None },
+        );
+        Box::new(values.filter_map(move |n| state.clone().unify(&x, &n.into_val())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::between;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal, Query, State};
+
+    #[test]
+    fn generates_range() {
+        let (low, x) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(low, -1), between(low, 1, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![-1, 0, 1]);
+    }
+
+    #[test]
+    fn checks_resolved_value() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![unify(x, 4), between(1, 3, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn empty_range() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![between(3, 1, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn includes_max_value() {
+        let x = var();
+        let goal: Goal<I32> = between(i32::MAX - 1, i32::MAX, x);
+        let results: Vec<_> = goal.query(x).collect();
+        assert_eq!(results, vec![i32::MAX - 1, i32::MAX]);
+    }
+
+    #[test]
+    fn enumerates_lazily() {
+        let x = var();
+        let goal: Goal<I32> = between(0, i32::MAX, x);
+        let state = goal.apply(State::new()).unwrap();
+        let results: Vec<_> = state.query(x).take(3).collect();
+        assert_eq!(results, vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,360 @@
+//! Ordering goals that keep track of the interval each var is known to fall
+//! in, so contradictions such as `x > 5, x < 3` fail as soon as both goals
+//! are applied.
+//!
+//! Unlike the plain [`lt`](crate::cmp::lt()) family, which only compare
+//! resolved values, both sides must be the same type and that type must be
+//! `Clone + 'static` so its bounds can be stored in the state.
+//!
+//! ```
+//! use canrun::{all, var, Goal, State};
+//! use canrun::domains::example::I32;
+//! use canrun_basic::cmp::bounds::{gt, lt};
+//!
+//! let (x, y) = (var(), var());
+//! let goal: Goal<I32> = all![gt(x, 5), lt(x, y), lt(y, 3)];
+//! assert!(goal.apply(State::new()).is_none());
+//! ```
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, LVar, Val};
+use canrun::DomainType;
+use im_rc::HashMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// One end of an [`Interval`].
+#[derive(Clone, Debug, PartialEq)]
+struct Bound<T> {
+    value: T,
+    inclusive: bool,
+}
+
+/// The range of values a var may still take.
+///
+/// Bounds are treated as continuous, so `x > 1, x < 2` is not considered
+/// empty even for integers.
+#[derive(Clone, Debug, PartialEq)]
+struct Interval<T> {
+    lower: Option<Bound<T>>,
+    upper: Option<Bound<T>>,
+}
+
+impl<T: PartialOrd + Clone> Interval<T> {
+    fn unbounded() -> Self {
+        Interval {
+            lower: None,
+            upper: None,
+        }
+    }
+
+    fn point(value: T) -> Self {
+        let bound = Some(Bound {
+            value,
+            inclusive: true,
+        });
+        Interval {
+            lower: bound.clone(),
+            upper: bound,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match (&self.lower, &self.upper) {
+            (Some(lower), Some(upper)) => {
+                lower.value > upper.value
+                    || lower.value == upper.value && !(lower.inclusive && upper.inclusive)
+            }
+            _ => false,
+        }
+    }
+
+    fn narrow_lower(self, bound: Option<Bound<T>>) -> Self {
+        let lower = match (self.lower, bound) {
+            (Some(a), Some(b)) if b.value > a.value || b.value == a.value && !b.inclusive => {
+                Some(b)
+            }
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        };
+        Interval { lower, ..self }
+    }
+
+    fn narrow_upper(self, bound: Option<Bound<T>>) -> Self {
+        let upper = match (self.upper, bound) {
+            (Some(a), Some(b)) if b.value < a.value || b.value == a.value && !b.inclusive => {
+                Some(b)
+            }
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        };
+        Interval { upper, ..self }
+    }
+}
+
+/// The known intervals of unresolved vars, kept as a
+/// [state extension](canrun::State::extension()).
+#[derive(Clone)]
+struct Bounds<T>(HashMap<LVar<T>, Interval<T>>);
+
+fn interval<'a, T, D>(state: &State<'a, D>, val: &Val<T>) -> Interval<T>
+where
+    T: PartialOrd + Clone + Debug + 'static,
+    D: DomainType<'a, T>,
+{
+    match val {
+        Val::Resolved(value) => Interval::point((**value).clone()),
+        Val::Var(var) => state
+            .extension::<Bounds<T>>()
+            .and_then(|bounds| bounds.0.get(var).cloned())
+            .unwrap_or_else(Interval::unbounded),
+    }
+}
+
+/// Ensure that one value is less than another, narrowing the bounds of
+/// both sides in the meantime.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal, State};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::cmp::bounds::lt;
+///
+/// let x = var();
+/// let goal: Goal<I32> = Goal::all(vec![lt(x, 3), lt(5, x)]);
+/// assert!(goal.apply(State::new()).is_none());
+/// ```
+pub fn lt<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: PartialOrd + Clone + Debug + 'static,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    below(a, b, true)
+}
+
+/// Ensure that one value is less than or equal to another, narrowing the
+/// bounds of both sides in the meantime.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal, State};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::cmp::bounds::lte;
+///
+/// let x = var();
+/// let goal: Goal<I32> = Goal::all(vec![lte(x, 3), lte(4, x)]);
+/// assert!(goal.apply(State::new()).is_none());
+/// ```
+pub fn lte<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: PartialOrd + Clone + Debug + 'static,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    below(a, b, false)
+}
+
+/// Ensure that one value is greater than another, narrowing the bounds of
+/// both sides in the meantime.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal, State};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::cmp::bounds::gt;
+///
+/// let x = var();
+/// let goal: Goal<I32> = Goal::all(vec![gt(x, 5), gt(3, x)]);
+/// assert!(goal.apply(State::new()).is_none());
+/// ```
+pub fn gt<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: PartialOrd + Clone + Debug + 'static,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    below(b, a, true)
+}
+
+/// Ensure that one value is greater than or equal to another, narrowing the
+/// bounds of both sides in the meantime.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal, State};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::cmp::bounds::gte;
+///
+/// let x = var();
+/// let goal: Goal<I32> = Goal::all(vec![gte(x, 4), gte(3, x)]);
+/// assert!(goal.apply(State::new()).is_none());
+/// ```
+pub fn gte<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: PartialOrd + Clone + Debug + 'static,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    below(b, a, false)
+}
+
+/// Create a goal relating two values with `<` (when `strict`) or `<=`.
+fn below<'a, T, A, B, D>(a: A, b: B, strict: bool) -> Goal<'a, D>
+where
+    T: PartialOrd + Clone + Debug + 'static,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    Goal::constraint(Below {
+        a: a.into_val(),
+        b: b.into_val(),
+        strict,
+    })
+}
+
+/// Keep `a` below `b`, narrowing the bounds of each side from the other
+/// until both are resolved.
+#[derive(Clone, Debug)]
+struct Below<T: Debug> {
+    a: Val<T>,
+    b: Val<T>,
+    strict: bool,
+}
+
+impl<'a, T, D> Constraint<'a, D> for Below<T>
+where
+    T: PartialOrd + Clone + Debug + 'static,
+    D: DomainType<'a, T>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let a = state.resolve_val(&self.a);
+        let b = state.resolve_val(&self.b);
+        let holds = match (a, b) {
+            (Val::Resolved(a), Val::Resolved(b)) if self.strict => Some(a < b),
+            (Val::Resolved(a), Val::Resolved(b)) => Some(a <= b),
+            (Val::Var(a), Val::Var(b)) if a == b => Some(!self.strict),
+            _ => None,
+        };
+        if let Some(holds) = holds {
+            return Ok(Box::new(
+                move |state| if holds { Some(state) } else { None },
+            ));
+        }
+
+        let (a_range, b_range) = (interval(state, a), interval(state, b));
+        let exclude = |bound: Option<Bound<T>>| {
+            bound.map(|Bound { value, inclusive }| Bound {
+                value,
+                inclusive: inclusive && !self.strict,
+            })
+        };
+        let a_narrowed = a_range.clone().narrow_upper(exclude(b_range.upper.clone()));
+        let b_narrowed = b_range.clone().narrow_lower(exclude(a_range.lower.clone()));
+        if a_narrowed.is_empty() || b_narrowed.is_empty() {
+            return Ok(Box::new(|_| None));
+        }
+
+        let mut changed = Vec::new();
+        let mut watch = Vec::new();
+        for (val, before, after) in [(a, a_range, a_narrowed), (b, b_range, b_narrowed)] {
+            if let Val::Var(var) = val {
+                watch.push(*var);
+                if before != after {
+                    changed.push((*var, after));
+                }
+            }
+        }
+        if changed.is_empty() {
+            return Err(match watch.as_slice() {
+                [a, b] => VarWatch::two(*a, *b),
+                [var] => VarWatch::one(*var),
+                _ => unreachable!("at least one side should be unresolved"),
+            });
+        }
+        let constraint = Rc::new(self.clone());
+        Ok(Box::new(move |state| {
+            let state = changed.into_iter().try_fold(state, |state, (var, range)| {
+                let mut bounds = state
+                    .extension::<Bounds<T>>()
+                    .cloned()
+                    .unwrap_or_else(|| Bounds(HashMap::new()));
+                bounds.0.insert(var, range);
+                state.set_extension(bounds).wake(var)
+            })?;
+            state.constrain(constraint)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gt, gte, lt, lte, Interval};
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal, State};
+
+    fn applies(goals: Vec<Goal<I32>>) -> bool {
+        Goal::all(goals).apply(State::new()).is_some()
+    }
+
+    #[test]
+    fn contradiction_fails_before_resolution() {
+        let x = var();
+        assert!(!applies(vec![gt(x, 5), lt(x, 3)]));
+        assert!(applies(vec![gt(x, 1), lt(x, 3)]));
+    }
+
+    #[test]
+    fn propagates_through_vars() {
+        let (x, y, z) = (var(), var(), var());
+        assert!(!applies(vec![lt(x, y), lt(y, z), lt(z, 0), gte(x, 0)]));
+        assert!(applies(vec![lt(x, y), lt(y, z), lt(z, 0), gte(x, -5)]));
+    }
+
+    #[test]
+    fn strictness_at_the_edges() {
+        let x = var();
+        assert!(applies(vec![gte(x, 2), lte(x, 2)]));
+        assert!(!applies(vec![gt(x, 2), lte(x, 2)]));
+        assert!(!applies(vec![lt(x, x)]));
+        assert!(applies(vec![lte(x, x)]));
+    }
+
+    #[test]
+    fn checks_bounds_when_bound() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![lt(x, y), lt(y, 3), unify(x, 4)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn merges_bounds_of_unified_vars() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![gt(x, 5), lt(y, 3), unify(x, y)];
+        assert!(!applies(goals));
+    }
+
+    #[test]
+    fn narrowing_keeps_tighter_bounds() {
+        let range = Interval::point(3)
+            .narrow_upper(Interval::point(5).upper)
+            .narrow_lower(Interval::unbounded().lower);
+        assert_eq!(range, Interval::point(3));
+        assert!(!range.is_empty());
+    }
+
+    #[test]
+    fn exclusive_point_is_empty() {
+        let mut range = Interval::point(3);
+        range.upper.as_mut().unwrap().inclusive = false;
+        assert!(range.is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::items::partition_items;
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::Val;
+use canrun::{DomainType, UnifyIn};
+use canrun_collections::lvec::LVec;
+use std::fmt::{self, Debug};
+
+/// Pick the item of an [`LVec`] that is preferred over all of the others
+/// once every item is resolved.
+pub(super) struct Extreme<T: Debug> {
+    pub(super) items: Val<LVec<T>>,
+    pub(super) result: Val<T>,
+    pub(super) prefer: fn(&T, &T) -> bool,
+}
+
+impl<T: Debug> Debug for Extreme<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Extreme {:?} {:?}", self.items, self.result)
+    }
+}
+
+impl<'a, T, D> Constraint<'a, D> for Extreme<T>
+where
+    T: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, T> + DomainType<'a, LVec<T>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let items = resolve_1(&self.items, state)?;
+        let (resolved, vars) = partition_items(&items, state);
+        if let Some(var) = vars.first() {
+            return Err(VarWatch::one(*var));
+        }
+        let prefer = self.prefer;
+        let found = resolved
+            .into_iter()
+            .reduce(|best, item| if prefer(&item, &best) { item } else { best })
+            .map(Val::Resolved);
+        let result = self.result.clone();
+        Ok(Box::new(move |state| state.unify(&found?, &result)))
+    }
+}
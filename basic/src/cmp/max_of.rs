@@ -0,0 +1,56 @@
+use super::extreme::Extreme;
+use canrun::goal::Goal;
+use canrun::value::IntoVal;
+use canrun::{DomainType, UnifyIn};
+use canrun_collections::lvec::LVec;
+use std::fmt::Debug;
+
+/// Get the greatest item in an [`LVec`].
+///
+/// This waits for every item to be resolved. An empty `LVec` fails.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::max_of;
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = max_of(lvec![3, 1, 2], x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![3]);
+/// ```
+pub fn max_of<'a, T, I, M, D>(items: I, max: M) -> Goal<'a, D>
+where
+    T: PartialOrd + UnifyIn<'a, D> + Debug + 'a,
+    I: IntoVal<LVec<T>>,
+    M: IntoVal<T>,
+    D: DomainType<'a, T> + DomainType<'a, LVec<T>>,
+{
+    Goal::constraint(Extreme {
+        items: items.into_val(),
+        result: max.into_val(),
+        prefer: |item, best| item > best,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::max_of;
+    use canrun::{unify, util, var, Goal};
+    use canrun_collections::{example::Collections, lvec};
+
+    #[test]
+    fn succeeds() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(x, -4), max_of(lvec![2, x, 3], y)];
+        util::assert_permutations_resolve_to(goals, y, vec![3]);
+    }
+
+    #[test]
+    fn empty_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![max_of(lvec![], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,33 @@
+//! Example domains for the Canrun basic goals
+use crate::approx::Approx;
+use canrun_collections::lvec::LVec;
+use num_rational::Rational64;
+
+canrun::domain! {
+    pub Strings {
+        String,
+        char,
+        usize,
+        LVec<char>,
+    }
+}
+
+canrun::domain! {
+    pub Bools {
+        bool,
+        LVec<bool>,
+    }
+}
+
+canrun::domain! {
+    pub Floats {
+        f64,
+        Approx<f64>,
+    }
+}
+
+canrun::domain! {
+    pub Rationals {
+        Rational64,
+    }
+}
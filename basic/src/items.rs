@@ -0,0 +1,28 @@
+//! Helpers for constraints over the items of an [`LVec`].
+use canrun::state::State;
+use canrun::value::{LVar, Val};
+use canrun::DomainType;
+use canrun_collections::lvec::LVec;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Split the items of an [`LVec`] into the values that are already resolved
+/// and the vars that are not.
+pub(crate) fn partition_items<'a, T, D>(
+    items: &LVec<T>,
+    state: &State<'a, D>,
+) -> (Vec<Rc<T>>, Vec<LVar<T>>)
+where
+    T: Debug,
+    D: DomainType<'a, T>,
+{
+    let mut resolved = Vec::new();
+    let mut vars = Vec::new();
+    for item in items.iter() {
+        match state.resolve_val(item) {
+            Val::Resolved(value) => resolved.push(value.clone()),
+            Val::Var(var) => vars.push(*var),
+        }
+    }
+    (resolved, vars)
+}
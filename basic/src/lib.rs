@@ -5,8 +5,14 @@
 
 extern crate canrun;
 
+pub mod approx;
 pub mod cmp;
+pub mod example;
+mod items;
+pub mod linear;
+pub mod logic;
 pub mod ops;
+pub mod str;
 
 #[doc(hidden)]
 pub use cmp::*;
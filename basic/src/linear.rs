@@ -0,0 +1,382 @@
+//! Linear constraints over numbers, solved together as one system.
+//!
+//! Each of [`eq`], [`leq`] and [`geq`] adds a row to a simplex-style solver
+//! kept in the [state extension](canrun::State::extension()). Whenever a row
+//! is added or one of its variables is bound, the rows connected to it are
+//! checked for feasibility, so contradictions fail as early as possible. Variables whose
+//! value is determined by the system are bound right away, even if nothing
+//! else unifies them.
+//!
+//! Comparisons are made exactly, so the numbers must be one of the
+//! [`num_rational`] ratio types, such as [`num_rational::Rational64`]. See
+//! [`Number`].
+//!
+//! # Example:
+//! ```
+//! use canrun::{all, unify, var, Goal};
+//! use canrun_basic::example::Rationals;
+//! use canrun_basic::linear::{self, LinExpr};
+//! use num_rational::Rational64;
+//!
+//! let q = Rational64::from_integer;
+//! let (rent, food, savings) = (var(), var(), var());
+//! let goal: Goal<Rationals> = all![
+//!     linear::eq(LinExpr::from(rent) + food + savings, q(3000)),
+//!     unify(rent, q(1200)),
+//!     linear::geq(savings, LinExpr::from(food) * q(2)),
+//!     linear::geq(food, q(600)),
+//! ];
+//! let result: Vec<_> = goal.query((food, savings)).collect();
+//! assert_eq!(result, vec![(q(600), q(1200))]);
+//! ```
+
+mod expr;
+mod simplex;
+
+pub use expr::LinExpr;
+pub use simplex::Number;
+
+use canrun::goal::{custom, Goal};
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{LVar, Val};
+use canrun::{DomainType, UnifyIn};
+use im_rc::Vector;
+use simplex::Tableau;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Require two linear expressions to be equal.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Rationals;
+/// use canrun_basic::linear::{self, LinExpr};
+/// use num_rational::Rational64;
+///
+/// let q = Rational64::from_integer;
+/// let (x, y) = (var(), var());
+/// let goal: Goal<Rationals> = Goal::all(vec![
+///     linear::eq(LinExpr::from(x) + y, q(10)),
+///     linear::eq(LinExpr::from(x) - y, q(3)),
+/// ]);
+/// let result: Vec<_> = goal.query((x, y)).collect();
+/// assert_eq!(result, vec![(Rational64::new(13, 2), Rational64::new(7, 2))]);
+/// ```
+pub fn eq<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: Number + Debug + UnifyIn<'a, D> + 'static,
+    A: Into<LinExpr<T>>,
+    B: Into<LinExpr<T>>,
+    D: DomainType<'a, T>,
+{
+    add_row(a.into() - b, true)
+}
+
+/// Require a linear expression to be less than or equal to another.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Rationals;
+/// use canrun_basic::linear;
+/// use num_rational::Rational64;
+///
+/// let q = Rational64::from_integer;
+/// let x = var();
+/// let goal: Goal<Rationals> = Goal::all(vec![
+///     linear::leq(x, q(2)),
+///     linear::leq(q(2), x),
+/// ]);
+/// let result: Vec<_> = goal.query(x).collect();
+/// assert_eq!(result, vec![q(2)]);
+/// ```
+pub fn leq<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: Number + Debug + UnifyIn<'a, D> + 'static,
+    A: Into<LinExpr<T>>,
+    B: Into<LinExpr<T>>,
+    D: DomainType<'a, T>,
+{
+    add_row(a.into() - b, false)
+}
+
+/// Require a linear expression to be greater than or equal to another.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Rationals;
+/// use canrun_basic::linear;
+/// use num_rational::Rational64;
+///
+/// let q = Rational64::from_integer;
+/// let x = var();
+/// let goal: Goal<Rationals> = Goal::all(vec![
+///     linear::geq(x, q(5)),
+///     linear::leq(x, q(3)),
+/// ]);
+/// assert_eq!(goal.query(x).count(), 0);
+/// ```
+pub fn geq<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: Number + Debug + UnifyIn<'a, D> + 'static,
+    A: Into<LinExpr<T>>,
+    B: Into<LinExpr<T>>,
+    D: DomainType<'a, T>,
+{
+    add_row(b.into() - a, false)
+}
+
+/// A row of `expr = 0` (when `equality`) or `expr <= 0`.
+#[derive(Clone, Debug)]
+struct Row<T: Debug> {
+    expr: LinExpr<T>,
+    equality: bool,
+}
+
+/// All of the rows not yet reduced to constants, stored as a state
+/// extension.
+#[derive(Clone, Debug)]
+struct Store<T: Clone + Debug>(Vector<Row<T>>);
+
+fn add_row<'a, T, D>(expr: LinExpr<T>, equality: bool) -> Goal<'a, D>
+where
+    T: Number + Debug + UnifyIn<'a, D> + 'static,
+    D: DomainType<'a, T>,
+{
+    custom(move |state| {
+        let vars = expr.terms.iter().map(|(var, _)| *var).collect();
+        let row = Row {
+            expr: expr.clone(),
+            equality,
+        };
+        let state = propagate::<T, D>(state, Some(row))?;
+        state.constrain(Rc::new(Watch { vars }))
+    })
+}
+
+/// Re-check the system whenever one of the vars from a row changes.
+#[derive(Debug)]
+struct Watch<T> {
+    vars: Vec<LVar<T>>,
+}
+
+impl<'a, T, D> Constraint<'a, D> for Watch<T>
+where
+    T: Number + Debug + UnifyIn<'a, D> + 'static,
+    D: DomainType<'a, T>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let mut remaining = Vec::new();
+        for var in &self.vars {
+            let val = Val::Var(*var);
+            if let Val::Var(resolved) = state.resolve_val(&val) {
+                if !remaining.contains(resolved) {
+                    remaining.push(*resolved);
+                }
+            }
+        }
+        if remaining == self.vars {
+            return Err(VarWatch::many(remaining));
+        }
+        Ok(Box::new(move |state| {
+            let state = propagate::<T, D>(state, None)?;
+            if remaining.is_empty() {
+                Some(state)
+            } else {
+                state.constrain(Rc::new(Watch { vars: remaining }))
+            }
+        }))
+    }
+}
+
+/// Substitute known values into the stored rows, fail if they can no longer
+/// be satisfied and bind any vars they fully determine.
+///
+/// Only the rows that were added or changed, along with any rows that share
+/// vars with them, are solved again. The rest were already checked. Values
+/// found for fixed vars are substituted into the stored rows before the vars
+/// are bound, so the [`Watch`] constraints woken by those bindings have
+/// nothing left to solve.
+fn propagate<'a, T, D>(state: State<'a, D>, added: Option<Row<T>>) -> Option<State<'a, D>>
+where
+    T: Number + Debug + UnifyIn<'a, D> + 'static,
+    D: DomainType<'a, T>,
+{
+    let stored = state
+        .extension::<Store<T>>()
+        .map(|store| store.0.clone())
+        .unwrap_or_default();
+
+    let mut rows = Vec::new();
+    let mut solve = Vec::new();
+    let stored = stored.into_iter().map(|row| (row, false));
+    for (row, is_new) in stored.chain(added.map(|row| (row, true))) {
+        let expr = row.expr.resolve(&state);
+        if expr.terms.is_empty() {
+            let holds = if row.equality {
+                expr.constant.is_zero()
+            } else {
+                !expr.constant.is_positive()
+            };
+            if !holds {
+                return None;
+            }
+            continue;
+        }
+        solve.push(is_new || expr != row.expr);
+        rows.push(Row {
+            expr,
+            equality: row.equality,
+        });
+    }
+
+    // Rows that share a var with a changed row may have changed too.
+    let mut vars: Vec<LVar<T>> = Vec::new();
+    let mut grew = true;
+    while grew {
+        grew = false;
+        for (row, solving) in rows.iter().zip(solve.iter_mut()) {
+            if !*solving && !row.expr.terms.iter().any(|(var, _)| vars.contains(var)) {
+                continue;
+            }
+            *solving = true;
+            for (var, _) in &row.expr.terms {
+                if !vars.contains(var) {
+                    vars.push(*var);
+                    grew = true;
+                }
+            }
+        }
+    }
+    if vars.is_empty() {
+        return Some(state.set_extension(Store(rows.into_iter().collect())));
+    }
+
+    let problem: Vec<_> = rows
+        .iter()
+        .zip(&solve)
+        .filter(|(_, solving)| **solving)
+        .map(|(row, _)| {
+            let mut coefficients = vec![T::zero(); vars.len()];
+            for (var, coefficient) in &row.expr.terms {
+                let index = vars
+                    .iter()
+                    .position(|v| v == var)
+                    .expect("var was collected");
+                coefficients[index] = coefficient.clone();
+            }
+            simplex::Row {
+                coefficients,
+                constant: row.expr.constant.clone(),
+                equality: row.equality,
+            }
+        })
+        .collect();
+    let tableau = Tableau::feasible(&problem, vars.len())?;
+    let fixed: Vec<_> = vars
+        .iter()
+        .enumerate()
+        .filter_map(|(index, var)| tableau.fixed(index).map(|value| (*var, value)))
+        .collect();
+
+    // Any row reduced to a constant here is satisfied by the feasible
+    // solution, so it can be dropped.
+    let rows = rows
+        .into_iter()
+        .map(|row| Row {
+            expr: row.expr.substitute(&fixed),
+            equality: row.equality,
+        })
+        .filter(|row| !row.expr.terms.is_empty())
+        .collect();
+    let state = state.set_extension(Store(rows));
+    fixed.into_iter().try_fold(state, |state, (var, value)| {
+        state.unify(&Val::Var(var), &Val::Resolved(Rc::new(value)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eq, geq, leq, LinExpr};
+    use crate::example::Rationals;
+    use canrun::{unify, util, var, Goal, State};
+    use num_rational::Rational64;
+
+    fn q(value: i64) -> Rational64 {
+        Rational64::from_integer(value)
+    }
+
+    #[test]
+    fn infeasible_fails_without_bindings() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Rationals>> =
+            vec![leq(LinExpr::from(x) + y, q(1)), geq(x, q(1)), geq(y, q(1))];
+        assert!(Goal::all(goals).apply(State::new()).is_none());
+    }
+
+    #[test]
+    fn binds_uniquely_determined_vars() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Rationals>> = vec![
+            eq(LinExpr::from(x) * q(2) + y, q(7)),
+            eq(LinExpr::from(x) - y, q(-1)),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(q(2), q(3))]);
+    }
+
+    #[test]
+    fn binds_after_a_var_resolves() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<Rationals>> = vec![
+            eq(LinExpr::from(x) + y + z, q(6)),
+            eq(LinExpr::from(x) - z, q(2)),
+            unify(y, q(0)),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, z), vec![(q(4), q(2))]);
+    }
+
+    #[test]
+    fn follows_unified_vars() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Rationals>> = vec![eq(LinExpr::from(x) + y, q(3)), unify(x, y)];
+        util::assert_permutations_resolve_to(
+            goals,
+            (x, y),
+            vec![(Rational64::new(3, 2), Rational64::new(3, 2))],
+        );
+    }
+
+    #[test]
+    fn solves_separate_systems() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<Rationals>> = vec![
+            eq(LinExpr::from(x) + y, q(5)),
+            leq(z, q(3)),
+            unify(y, q(2)),
+            geq(z, q(3)),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, z), vec![(q(3), q(3))]);
+    }
+
+    #[test]
+    fn does_not_truncate() {
+        let x = var();
+        let goals: Vec<Goal<Rationals>> = vec![eq(LinExpr::from(x) * q(2), q(3))];
+        util::assert_permutations_resolve_to(goals, x, vec![Rational64::new(3, 2)]);
+
+        let goals: Vec<Goal<Rationals>> = vec![eq(LinExpr::from(x) * q(2), q(3)), unify(x, q(1))];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn checks_resolved_rows() {
+        let x = var();
+        let goals: Vec<Goal<Rationals>> = vec![leq(x, q(2)), unify(x, q(3))];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
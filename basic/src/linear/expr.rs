@@ -0,0 +1,166 @@
+use canrun::state::State;
+use canrun::value::{LVar, Val};
+use canrun::DomainType;
+use num_traits::Num;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A linear expression: a sum of variables multiplied by constant
+/// coefficients, plus a constant.
+///
+/// Expressions are usually built from [`LVar`]s and constants with `+`, `-`
+/// and scaling by `*`. Anything that converts into a `LinExpr` can be used on
+/// the right hand side.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, LVar};
+/// use canrun_basic::linear::LinExpr;
+/// use num_rational::Rational64;
+///
+/// let (x, y): (LVar<Rational64>, LVar<Rational64>) = (var(), var());
+/// // 2x - y + 1
+/// let expr = LinExpr::from(x) * Rational64::from_integer(2) - y + Rational64::from_integer(1);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinExpr<T: Debug> {
+    pub(super) terms: Vec<(LVar<T>, T)>,
+    pub(super) constant: T,
+}
+
+impl<T: Num + Clone + Debug> LinExpr<T> {
+    /// Create an expression with a single constant value.
+    pub fn constant(value: T) -> Self {
+        LinExpr {
+            terms: Vec::new(),
+            constant: value,
+        }
+    }
+
+    /// Create an expression with a single variable multiplied by a
+    /// coefficient.
+    pub fn term(coefficient: T, var: LVar<T>) -> Self {
+        LinExpr::constant(T::zero()).with_term(var, coefficient)
+    }
+
+    fn with_term(mut self, var: LVar<T>, coefficient: T) -> Self {
+        match self.terms.iter().position(|(v, _)| *v == var) {
+            Some(index) => {
+                let sum = self.terms[index].1.clone() + coefficient;
+                if sum.is_zero() {
+                    self.terms.remove(index);
+                } else {
+                    self.terms[index].1 = sum;
+                }
+            }
+            None if coefficient.is_zero() => {}
+            None => self.terms.push((var, coefficient)),
+        }
+        self
+    }
+
+    /// Substitute the values of any resolved variables, and follow variables
+    /// that have been bound to other variables.
+    pub(super) fn resolve<'a, D>(&self, state: &State<'a, D>) -> Self
+    where
+        D: DomainType<'a, T>,
+    {
+        let mut resolved = LinExpr::constant(self.constant.clone());
+        for (var, coefficient) in &self.terms {
+            let val = Val::Var(*var);
+            match state.resolve_val(&val) {
+                Val::Resolved(value) => {
+                    resolved.constant = resolved.constant + coefficient.clone() * (**value).clone();
+                }
+                Val::Var(var) => resolved = resolved.with_term(*var, coefficient.clone()),
+            }
+        }
+        resolved
+    }
+
+    /// Replace any of the given vars with their values.
+    pub(super) fn substitute(&self, values: &[(LVar<T>, T)]) -> Self {
+        let mut substituted = LinExpr::constant(self.constant.clone());
+        for (var, coefficient) in &self.terms {
+            match values.iter().find(|(v, _)| v == var) {
+                Some((_, value)) => {
+                    substituted.constant =
+                        substituted.constant + coefficient.clone() * value.clone();
+                }
+                None => substituted = substituted.with_term(*var, coefficient.clone()),
+            }
+        }
+        substituted
+    }
+}
+
+impl<T: Num + Clone + Debug> From<T> for LinExpr<T> {
+    fn from(value: T) -> Self {
+        LinExpr::constant(value)
+    }
+}
+
+impl<T: Num + Clone + Debug> From<LVar<T>> for LinExpr<T> {
+    fn from(var: LVar<T>) -> Self {
+        LinExpr::term(T::one(), var)
+    }
+}
+
+impl<T: Num + Clone + Debug, R: Into<LinExpr<T>>> Add<R> for LinExpr<T> {
+    type Output = Self;
+    fn add(self, other: R) -> Self {
+        let other = other.into();
+        let start = LinExpr {
+            constant: self.constant + other.constant,
+            ..self
+        };
+        other
+            .terms
+            .into_iter()
+            .fold(start, |expr, (var, coefficient)| {
+                expr.with_term(var, coefficient)
+            })
+    }
+}
+
+impl<T: Num + Clone + Debug, R: Into<LinExpr<T>>> Sub<R> for LinExpr<T> {
+    type Output = Self;
+    fn sub(self, other: R) -> Self {
+        self + -other.into()
+    }
+}
+
+impl<T: Num + Clone + Debug> Neg for LinExpr<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self * (T::zero() - T::one())
+    }
+}
+
+impl<T: Num + Clone + Debug> Mul<T> for LinExpr<T> {
+    type Output = Self;
+    fn mul(self, factor: T) -> Self {
+        let start = LinExpr::constant(self.constant * factor.clone());
+        self.terms
+            .into_iter()
+            .fold(start, |expr, (var, coefficient)| {
+                expr.with_term(var, coefficient * factor.clone())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinExpr;
+    use canrun::{var, LVar};
+    use num_rational::Rational64;
+
+    #[test]
+    fn combines_like_terms() {
+        let q = Rational64::from_integer;
+        let (x, y): (LVar<Rational64>, LVar<Rational64>) = (var(), var());
+        let expr = (LinExpr::from(x) + y + q(2)) * q(3) - LinExpr::term(q(3), y) - x;
+        assert_eq!(expr.terms, vec![(x, q(2))]);
+        assert_eq!(expr.constant, q(6));
+    }
+}
@@ -0,0 +1,262 @@
+use num_traits::{Num, Signed};
+use std::cmp::Ordering;
+
+/// The number types the solver can work with.
+///
+/// Pivoting divides, so only exact field types are allowed. This is
+/// implemented for the [`num_rational`] ratios and can't be implemented
+/// outside of this crate. Integers are rejected, since their division
+/// truncates and would give wrong answers:
+///
+/// ```compile_fail
+/// use canrun::{var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::linear::{self, LinExpr};
+///
+/// let x = var();
+/// let goal: Goal<I32> = linear::eq(LinExpr::from(x) * 2, 3);
+/// ```
+pub trait Number: sealed::Sealed + Num + Signed + Ord + Clone {}
+
+mod sealed {
+    use num_rational::Ratio;
+
+    pub trait Sealed {}
+
+    macro_rules! impl_number {
+        ($($int:ty),+) => {
+            $(
+                impl Sealed for Ratio<$int> {}
+                impl super::Number for Ratio<$int> {}
+            )+
+        };
+    }
+
+    impl_number!(i8, i16, i32, i64, i128, isize);
+}
+
+/// A row of `coefficients · x + constant`, which must either equal zero or be
+/// at most zero.
+pub(super) struct Row<T> {
+    pub(super) coefficients: Vec<T>,
+    pub(super) constant: T,
+    pub(super) equality: bool,
+}
+
+/// A feasible simplex tableau in standard form.
+///
+/// The problem variables are unrestricted, so each is split into a positive
+/// and negative part in columns `2j` and `2j + 1`. Inequalities get their own
+/// slack column after those.
+#[derive(Clone)]
+pub(super) struct Tableau<T> {
+    rows: Vec<Vec<T>>,
+    rhs: Vec<T>,
+    basis: Vec<usize>,
+}
+
+impl<T: Number> Tableau<T> {
+    /// Find a feasible solution to the rows, or `None` if there is none.
+    pub(super) fn feasible(problem: &[Row<T>], vars: usize) -> Option<Self> {
+        let slacks = problem.iter().filter(|row| !row.equality).count();
+        let usable = 2 * vars + slacks;
+        let columns = usable + problem.len();
+        let mut tableau = Tableau {
+            rows: Vec::with_capacity(problem.len()),
+            rhs: Vec::with_capacity(problem.len()),
+            basis: Vec::with_capacity(problem.len()),
+        };
+        let mut slack = 2 * vars;
+        for (index, row) in problem.iter().enumerate() {
+            let mut cells = vec![T::zero(); columns];
+            for (var, coefficient) in row.coefficients.iter().enumerate() {
+                cells[2 * var] = coefficient.clone();
+                cells[2 * var + 1] = -coefficient.clone();
+            }
+            if !row.equality {
+                cells[slack] = T::one();
+                slack += 1;
+            }
+            let mut rhs = -row.constant.clone();
+            if rhs.is_negative() {
+                cells.iter_mut().for_each(|cell| *cell = -cell.clone());
+                rhs = -rhs;
+            }
+            // Artificial variables start out as the basis
+            cells[usable + index] = T::one();
+            tableau.rows.push(cells);
+            tableau.rhs.push(rhs);
+            tableau.basis.push(usable + index);
+        }
+
+        let mut cost = vec![T::zero(); columns];
+        cost[usable..].iter_mut().for_each(|cell| *cell = T::one());
+        // The artificial sum is bounded below by zero, so this cannot be
+        // unbounded.
+        tableau.optimize(&cost);
+        if tableau.objective(&cost).is_positive() {
+            return None;
+        }
+
+        // Drive any remaining (zero valued) artificials out of the basis,
+        // dropping rows that turn out to be redundant.
+        let mut index = 0;
+        while index < tableau.rows.len() {
+            if tableau.basis[index] >= usable {
+                match (0..usable).find(|&col| !tableau.rows[index][col].is_zero()) {
+                    Some(col) => tableau.pivot(index, col),
+                    None => {
+                        tableau.rows.remove(index);
+                        tableau.rhs.remove(index);
+                        tableau.basis.remove(index);
+                        continue;
+                    }
+                }
+            }
+            index += 1;
+        }
+        tableau.rows.iter_mut().for_each(|row| row.truncate(usable));
+        Some(tableau)
+    }
+
+    /// Get the value of a problem variable if every feasible solution agrees
+    /// on it.
+    pub(super) fn fixed(&self, var: usize) -> Option<T> {
+        let min = self.minimize(var, T::one())?;
+        let max = -self.minimize(var, -T::one())?;
+        if min == max {
+            Some(min)
+        } else {
+            None
+        }
+    }
+
+    /// Minimize `direction * x[var]`, returning `None` if it is unbounded.
+    fn minimize(&self, var: usize, direction: T) -> Option<T> {
+        let mut tableau = self.clone();
+        let mut cost = vec![T::zero(); self.rows.first().map_or(2 * (var + 1), Vec::len)];
+        cost[2 * var] = direction.clone();
+        cost[2 * var + 1] = -direction;
+        if tableau.optimize(&cost) {
+            Some(tableau.objective(&cost))
+        } else {
+            None
+        }
+    }
+
+    fn objective(&self, cost: &[T]) -> T {
+        self.basis
+            .iter()
+            .zip(&self.rhs)
+            .fold(T::zero(), |sum, (&col, rhs)| {
+                sum + cost[col].clone() * rhs.clone()
+            })
+    }
+
+    /// Pivot until the cost is minimized, using Bland's rule to avoid
+    /// cycling. Returns `false` if the cost is unbounded.
+    fn optimize(&mut self, cost: &[T]) -> bool {
+        loop {
+            let entering = (0..cost.len()).find(|&col| {
+                let reduced = self
+                    .basis
+                    .iter()
+                    .zip(&self.rows)
+                    .fold(cost[col].clone(), |reduced, (&basic, row)| {
+                        reduced - cost[basic].clone() * row[col].clone()
+                    });
+                reduced.is_negative()
+            });
+            let col = match entering {
+                Some(col) => col,
+                None => return true,
+            };
+            let leaving = (0..self.rows.len())
+                .filter(|&row| self.rows[row][col].is_positive())
+                .min_by(|&a, &b| {
+                    let ratio_a = self.rhs[a].clone() / self.rows[a][col].clone();
+                    let ratio_b = self.rhs[b].clone() / self.rows[b][col].clone();
+                    match ratio_a.cmp(&ratio_b) {
+                        Ordering::Equal => self.basis[a].cmp(&self.basis[b]),
+                        order => order,
+                    }
+                });
+            match leaving {
+                Some(row) => self.pivot(row, col),
+                None => return false,
+            }
+        }
+    }
+
+    fn pivot(&mut self, row: usize, col: usize) {
+        let pivot = self.rows[row][col].clone();
+        self.rows[row]
+            .iter_mut()
+            .for_each(|cell| *cell = cell.clone() / pivot.clone());
+        self.rhs[row] = self.rhs[row].clone() / pivot;
+        for other in 0..self.rows.len() {
+            let factor = self.rows[other][col].clone();
+            if other == row || factor.is_zero() {
+                continue;
+            }
+            for index in 0..self.rows[other].len() {
+                let delta = factor.clone() * self.rows[row][index].clone();
+                self.rows[other][index] = self.rows[other][index].clone() - delta;
+            }
+            self.rhs[other] = self.rhs[other].clone() - factor * self.rhs[row].clone();
+        }
+        self.basis[row] = col;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Row, Tableau};
+    use num_rational::Rational64;
+
+    fn row(coefficients: &[i64], constant: i64, equality: bool) -> Row<Rational64> {
+        Row {
+            coefficients: coefficients
+                .iter()
+                .map(|c| Rational64::from_integer(*c))
+                .collect(),
+            constant: Rational64::from_integer(constant),
+            equality,
+        }
+    }
+
+    #[test]
+    fn detects_infeasibility() {
+        // x >= 5, x <= 3
+        let problem = vec![row(&[-1], 5, false), row(&[1], -3, false)];
+        assert!(Tableau::feasible(&problem, 1).is_none());
+    }
+
+    #[test]
+    fn finds_fixed_values() {
+        // x + y = 10, x - y = 4, z <= 1
+        let problem = vec![
+            row(&[1, 1, 0], -10, true),
+            row(&[1, -1, 0], -4, true),
+            row(&[0, 0, 1], -1, false),
+        ];
+        let tableau = Tableau::feasible(&problem, 3).unwrap();
+        assert_eq!(tableau.fixed(0), Some(Rational64::from_integer(7)));
+        assert_eq!(tableau.fixed(1), Some(Rational64::from_integer(3)));
+        assert_eq!(tableau.fixed(2), None);
+    }
+
+    #[test]
+    fn handles_redundant_rows() {
+        // x + y = 2, 2x + 2y = 4, x >= y, x <= y
+        let problem = vec![
+            row(&[1, 1], -2, true),
+            row(&[2, 2], -4, true),
+            row(&[-1, 1], 0, false),
+            row(&[1, -1], 0, false),
+        ];
+        let tableau = Tableau::feasible(&problem, 2).unwrap();
+        assert_eq!(tableau.fixed(0), Some(Rational64::from_integer(1)));
+        assert_eq!(tableau.fixed(1), Some(Rational64::from_integer(1)));
+    }
+}
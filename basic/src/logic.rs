@@ -0,0 +1,34 @@
+//! Boolean relations including [`and`](and::and), [`or`](or::or),
+//! [`xor`](xor::xor), [`not`](not::not) and [`implies`](implies::implies),
+//! plus a [`clause`](clause::clause) constraint over an
+//! [`LVec`](canrun_collections::lvec::LVec) of bools.
+//!
+//! The relations propagate as soon as enough operands are known, instead of
+//! waiting for all of them. For example, `and(x, y, false)` binds `y` to
+//! `false` as soon as `x` is `true`.
+//!
+//! ```
+//! use canrun::{all, unify, var, Goal};
+//! use canrun_basic::example::Bools;
+//! use canrun_basic::logic::and;
+//!
+//! let (x, y) = (var(), var());
+//! let goal: Goal<Bools> = all![and(x, y, false), unify(x, true)];
+//! let results: Vec<_> = goal.query(y).collect();
+//! assert_eq!(results, vec![false]);
+//! ```
+
+mod and;
+mod clause;
+mod implies;
+mod not;
+mod or;
+mod table;
+mod xor;
+
+pub use and::and;
+pub use clause::clause;
+pub use implies::implies;
+pub use not::not;
+pub use or::or;
+pub use xor::xor;
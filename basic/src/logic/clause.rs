@@ -0,0 +1,122 @@
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, LVar, Val};
+use canrun::{DomainType, UnifyIn};
+use canrun_collections::lvec::LVec;
+use std::rc::Rc;
+
+/// Require at least one of the bools in an [`LVec`] to be `true`.
+///
+/// Once every literal but one is known to be `false`, the last one is bound
+/// to `true` (unit propagation). Combine with [`not`](super::not) to use
+/// negated literals, which makes it easy to encode rules such as "`http2`
+/// requires `tls`".
+///
+/// # Example:
+/// ```
+/// use canrun::{all, unify, var, Goal};
+/// use canrun_basic::example::Bools;
+/// use canrun_basic::logic::{clause, not};
+/// use canrun_collections::lvec;
+///
+/// let (tls, http2, no_http2) = (var(), var(), var());
+/// let goal: Goal<Bools> = all![
+///     not(http2, no_http2),
+///     clause(lvec![no_http2, tls]),
+///     unify(http2, true),
+/// ];
+/// let results: Vec<_> = goal.query(tls).collect();
+/// assert_eq!(results, vec![true]);
+/// ```
+pub fn clause<'a, L, D>(literals: L) -> Goal<'a, D>
+where
+    L: IntoVal<LVec<bool>>,
+    LVec<bool>: UnifyIn<'a, D>,
+    D: DomainType<'a, bool> + DomainType<'a, LVec<bool>>,
+{
+    Goal::constraint(Clause {
+        literals: literals.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Clause {
+    literals: Val<LVec<bool>>,
+}
+
+impl<'a, D> Constraint<'a, D> for Clause
+where
+    LVec<bool>: UnifyIn<'a, D>,
+    D: DomainType<'a, bool> + DomainType<'a, LVec<bool>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let literals = resolve_1(&self.literals, state)?;
+        let mut open: Vec<LVar<bool>> = Vec::new();
+        for literal in literals.iter() {
+            match state.resolve_val(literal) {
+                Val::Resolved(value) if **value => return Ok(Box::new(Some)),
+                Val::Resolved(_) => {}
+                Val::Var(var) if !open.contains(var) => open.push(*var),
+                Val::Var(_) => {}
+            }
+        }
+        match open.as_slice() {
+            [] => Ok(Box::new(|_| None)),
+            [var] => {
+                let var = *var;
+                Ok(Box::new(move |state| {
+                    state.unify(&Val::Var(var), &Val::Resolved(Rc::new(true)))
+                }))
+            }
+            // Nothing can be learned until one of two open literals is known
+            [a, b, ..] => Err(VarWatch::two(*a, *b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clause;
+    use crate::example::Bools;
+    use canrun::{unify, util, var, Goal};
+    use canrun_collections::lvec;
+
+    #[test]
+    fn unit_propagation() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<Bools>> =
+            vec![clause(lvec![x, y, z]), unify(x, false), unify(z, false)];
+        util::assert_permutations_resolve_to(goals, y, vec![true]);
+    }
+
+    #[test]
+    fn satisfied_by_any_true() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![clause(lvec![x, y, false]), unify(y, true)];
+        util::assert_permutations_resolve_to(goals, y, vec![true]);
+    }
+
+    #[test]
+    fn fails_when_all_false() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![clause(lvec![x, y]), unify(x, false), unify(y, false)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![]);
+    }
+
+    #[test]
+    fn empty_clause_fails() {
+        let x = var();
+        let goals: Vec<Goal<Bools>> = vec![clause(x), unify(x, lvec![])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn repeated_literal() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![clause(lvec![x, y, x]), unify(y, false)];
+        util::assert_permutations_resolve_to(goals, x, vec![true]);
+    }
+}
@@ -0,0 +1,47 @@
+use canrun::goal::Goal;
+use canrun::map_1;
+use canrun::value::IntoVal;
+use canrun::DomainType;
+
+/// Relate a bool to its negation.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Bools;
+/// use canrun_basic::logic::not;
+///
+/// let x = var();
+/// let goal: Goal<Bools> = not(x, true);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![false]);
+/// ```
+pub fn not<'a, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    A: IntoVal<bool>,
+    B: IntoVal<bool>,
+    D: DomainType<'a, bool>,
+{
+    map_1(a, b, |a| !*a, |b| !*b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::not;
+    use crate::example::Bools;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![not(x, y), unify(y, false)];
+        util::assert_permutations_resolve_to(goals, x, vec![true]);
+    }
+
+    #[test]
+    fn fails() {
+        let x = var();
+        let goals: Vec<Goal<Bools>> = vec![not(x, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
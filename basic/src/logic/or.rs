@@ -0,0 +1,64 @@
+use super::table::table;
+use canrun::goal::Goal;
+use canrun::value::IntoVal;
+use canrun::DomainType;
+
+/// Relate two bools to whether either of them is `true`.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Bools;
+/// use canrun_basic::logic::or;
+///
+/// let x = var();
+/// let goal: Goal<Bools> = or(false, x, true);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![true]);
+/// ```
+pub fn or<'a, A, B, C, D>(a: A, b: B, c: C) -> Goal<'a, D>
+where
+    A: IntoVal<bool>,
+    B: IntoVal<bool>,
+    C: IntoVal<bool>,
+    D: DomainType<'a, bool>,
+{
+    table(vec![a.into_val(), b.into_val(), c.into_val()], |values| {
+        values[2] == (values[0] || values[1])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::or;
+    use crate::example::Bools;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn propagates_from_one_operand_and_result() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![or(x, y, true), unify(x, false)];
+        util::assert_permutations_resolve_to(goals, y, vec![true]);
+    }
+
+    #[test]
+    fn false_result_binds_both_operands() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<Bools>> = vec![or(x, y, z), unify(z, false)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(false, false)]);
+    }
+
+    #[test]
+    fn true_operand_binds_result() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<Bools>> = vec![or(x, y, z), unify(x, true)];
+        util::assert_permutations_resolve_to(goals, z, vec![true]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![or(x, y, false), unify(y, true)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
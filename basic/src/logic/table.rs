@@ -0,0 +1,101 @@
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{LVar, Val};
+use canrun::DomainType;
+use std::rc::Rc;
+
+type Relation = fn(&[bool]) -> bool;
+
+/// Create a goal requiring the `relation` to hold for the operands.
+///
+/// Any operand that has the same value in every assignment satisfying the
+/// relation is bound immediately.
+pub(super) fn table<'a, D>(operands: Vec<Val<bool>>, relation: Relation) -> Goal<'a, D>
+where
+    D: DomainType<'a, bool>,
+{
+    Goal::constraint(Table { operands, relation })
+}
+
+#[derive(Clone, Debug)]
+struct Table {
+    operands: Vec<Val<bool>>,
+    relation: Relation,
+}
+
+impl<'a, D> Constraint<'a, D> for Table
+where
+    D: DomainType<'a, bool>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let operands: Vec<_> = self
+            .operands
+            .iter()
+            .map(|val| state.resolve_val(val))
+            .collect();
+        let mut vars: Vec<LVar<bool>> = Vec::new();
+        for val in &operands {
+            if let Val::Var(var) = val {
+                if !vars.contains(var) {
+                    vars.push(*var);
+                }
+            }
+        }
+
+        // Which values each var takes across the satisfying assignments
+        let mut seen = vec![(false, false); vars.len()];
+        let mut satisfying = 0_usize;
+        for assignment in 0..(1_usize << vars.len()) {
+            let bit = |index: usize| assignment & (1 << index) != 0;
+            let values: Vec<bool> = operands
+                .iter()
+                .map(|val| match val {
+                    Val::Resolved(value) => **value,
+                    Val::Var(var) => bit(vars.iter().position(|v| v == var).unwrap()),
+                })
+                .collect();
+            if (self.relation)(&values) {
+                satisfying += 1;
+                for (index, seen) in seen.iter_mut().enumerate() {
+                    if bit(index) {
+                        seen.1 = true;
+                    } else {
+                        seen.0 = true;
+                    }
+                }
+            }
+        }
+        if satisfying == 0 {
+            return Ok(Box::new(|_| None));
+        }
+
+        let forced: Vec<(LVar<bool>, bool)> = vars
+            .iter()
+            .zip(seen)
+            .filter_map(|(var, seen)| match seen {
+                (true, false) => Some((*var, false)),
+                (false, true) => Some((*var, true)),
+                _ => None,
+            })
+            .collect();
+        // Once the forced vars are bound, any others may take any value
+        let done = satisfying == 1 << (vars.len() - forced.len());
+        if forced.is_empty() && !done {
+            return Err(VarWatch::many(vars));
+        }
+        let constraint = Rc::new(self.clone());
+        Ok(Box::new(move |state| {
+            let state = forced.into_iter().try_fold(state, |state, (var, value)| {
+                state.unify(&Val::Var(var), &Val::Resolved(Rc::new(value)))
+            })?;
+            if done {
+                Some(state)
+            } else {
+                state.constrain(constraint)
+            }
+        }))
+    }
+}
@@ -0,0 +1,57 @@
+use super::table::table;
+use canrun::goal::Goal;
+use canrun::value::IntoVal;
+use canrun::DomainType;
+
+/// Relate two bools to whether exactly one of them is `true`.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Bools;
+/// use canrun_basic::logic::xor;
+///
+/// let x = var();
+/// let goal: Goal<Bools> = xor(true, x, false);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![true]);
+/// ```
+pub fn xor<'a, A, B, C, D>(a: A, b: B, c: C) -> Goal<'a, D>
+where
+    A: IntoVal<bool>,
+    B: IntoVal<bool>,
+    C: IntoVal<bool>,
+    D: DomainType<'a, bool>,
+{
+    table(vec![a.into_val(), b.into_val(), c.into_val()], |values| {
+        values[2] == (values[0] != values[1])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::xor;
+    use crate::example::Bools;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn propagates_from_any_two() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<Bools>> = vec![xor(x, y, z), unify(x, true), unify(z, true)];
+        util::assert_permutations_resolve_to(goals, y, vec![false]);
+    }
+
+    #[test]
+    fn shared_operands() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![xor(x, x, y)];
+        util::assert_permutations_resolve_to(goals, y, vec![false]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Bools>> = vec![xor(x, y, true), unify(x, true), unify(y, true)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
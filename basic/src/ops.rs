@@ -1,12 +1,46 @@
 //! Operator goals including [`add`](add::add), [`sub`](sub::sub),
-//! [`mul`](mul::mul) and [`div`](div::div).
+//! [`mul`](mul::mul), [`div`](div::div), [`rem`](rem::rem),
+//! [`pow`](pow::pow), [`neg`](neg::neg), [`abs`](abs::abs) and
+//! [`sign`](sign::sign).
+//!
+//! [`sum`](sum::sum) and [`product`](product::product) combine every item in
+//! an [`LVec`](canrun_collections::lvec::LVec).
+//!
+//! The [`checked_add`](checked_add::checked_add),
+//! [`checked_sub`](checked_sub::checked_sub),
+//! [`checked_mul`](checked_mul::checked_mul) and
+//! [`checked_div`](checked_div::checked_div) variants fail instead of
+//! overflowing or dividing by zero.
 
+mod abs;
 mod add;
+mod checked_add;
+mod checked_div;
+mod checked_mul;
+mod checked_sub;
 mod div;
+mod fold;
 mod mul;
+mod neg;
+mod pow;
+mod product;
+mod rem;
+mod sign;
 mod sub;
+mod sum;
 
+pub use abs::abs;
 pub use add::add;
+pub use checked_add::checked_add;
+pub use checked_div::checked_div;
+pub use checked_mul::checked_mul;
+pub use checked_sub::checked_sub;
 pub use div::div;
 pub use mul::mul;
+pub use neg::neg;
+pub use pow::pow;
+pub use product::product;
+pub use rem::rem;
+pub use sign::sign;
 pub use sub::sub;
+pub use sum::sum;
@@ -0,0 +1,117 @@
+use canrun::goal::{unify, Goal};
+use canrun::state::{
+    constraints::{Constraint, OneOfTwo, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use num_traits::{CheckedNeg, Signed};
+use std::fmt::Debug;
+
+/// Relate a value to its absolute value.
+///
+/// When only the absolute value is known, the state is forked for both the
+/// positive and negative pre-image. Values whose absolute value overflows,
+/// such as `i32::MIN`, fail.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::abs;
+///
+/// let x = var();
+/// let goal: Goal<I32> = abs(x, 3);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![3, -3]);
+/// ```
+pub fn abs<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: Signed + CheckedNeg + PartialOrd + UnifyIn<'a, D> + Debug + Copy + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    Goal::constraint(Abs {
+        a: a.into_val(),
+        b: b.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Abs<T: Debug> {
+    a: Val<T>,
+    b: Val<T>,
+}
+
+impl<'a, T, D> Constraint<'a, D> for Abs<T>
+where
+    T: Signed + CheckedNeg + PartialOrd + UnifyIn<'a, D> + Debug + Copy + 'a,
+    D: DomainType<'a, T>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        match OneOfTwo::resolve(&self.a, &self.b, state)? {
+            OneOfTwo::A(a, b) => {
+                let abs = if a.is_negative() {
+                    a.checked_neg()
+                } else {
+                    Some(*a)
+                };
+                Ok(Box::new(move |state| state.unify(&abs?.into_val(), &b)))
+            }
+            OneOfTwo::B(a, b) => {
+                let b = *b;
+                let goal = if b.is_negative() {
+                    Goal::fail()
+                } else if b.is_zero() {
+                    unify(a, b)
+                } else {
+                    Goal::any(vec![unify(a.clone(), b), unify(a, -b)])
+                };
+                Ok(Box::new(|state| goal.apply(state)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::abs;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn forwards() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, -2), abs(x, y)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(-2, 2)]);
+    }
+
+    #[test]
+    fn zero_has_one_pre_image() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![abs(x, 0)];
+        util::assert_permutations_resolve_to(goals, x, vec![0]);
+    }
+
+    #[test]
+    fn negative_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![abs(x, -1)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![abs(i32::MIN, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn reaches_edges() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![abs(x, i32::MAX)];
+        util::assert_permutations_resolve_to(goals, x, vec![i32::MAX, -i32::MAX]);
+    }
+}
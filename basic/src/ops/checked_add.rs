@@ -0,0 +1,67 @@
+use canrun::goal::Goal;
+use canrun::map_2_opt;
+use canrun::value::IntoVal;
+use canrun::{DomainType, UnifyIn};
+use num_traits::{CheckedAdd, CheckedSub};
+use std::fmt::Debug;
+
+/// Add two values together, failing instead of overflowing.
+///
+/// Like [`add`](crate::add), but any direction that would overflow fails
+/// the goal.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::checked_add;
+///
+/// let x = var();
+/// let goal: Goal<I32> = checked_add(i32::MAX, 1, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![]);
+/// ```
+pub fn checked_add<'a, T, A, B, C, D>(a: A, b: B, c: C) -> Goal<'a, D>
+where
+    T: CheckedAdd + CheckedSub + UnifyIn<'a, D> + Debug + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    C: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    map_2_opt(
+        a,
+        b,
+        c,
+        |a, b| a.checked_add(b),
+        |a, c| c.checked_sub(a),
+        |b, c| c.checked_sub(b),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::checked_add;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> =
+            vec![unify(x, 1), unify(y, 2), unify(z, 3), checked_add(x, y, z)];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![(1, 2, 3)]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> = vec![
+            unify(x, i32::MAX),
+            unify(y, 1),
+            unify(z, i32::MIN),
+            checked_add(x, y, z),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![]);
+    }
+}
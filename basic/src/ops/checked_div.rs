@@ -0,0 +1,77 @@
+use canrun::goal::project::project_2;
+use canrun::goal::{unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use num_traits::CheckedDiv;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Divide one value with another, failing instead of panicking.
+///
+/// Like [`div`](crate::div), but dividing by zero or overflowing fails the
+/// goal instead. This only works forwards, since integer division truncates
+/// and many dividends share the same quotient.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::checked_div;
+///
+/// let x = var();
+/// let goal: Goal<I32> = checked_div(3, 0, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![]);
+/// ```
+pub fn checked_div<'a, T, A, B, C, D>(a: A, b: B, c: C) -> Goal<'a, D>
+where
+    T: CheckedDiv + UnifyIn<'a, D> + Debug + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    C: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    let c: Val<T> = c.into_val();
+    project_2(a, b, move |a: Rc<T>, b: Rc<T>| match a.checked_div(&b) {
+        Some(quotient) => unify(c.clone(), quotient),
+        None => Goal::fail(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::checked_div;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> =
+            vec![unify(x, 6), unify(y, 2), unify(z, 3), checked_div(x, y, z)];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![(6, 2, 3)]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, 6), unify(y, 0), checked_div(x, y, z)];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![]);
+    }
+
+    #[test]
+    fn does_not_derive_inputs() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![checked_div(6, x, 4), unify(y, 1)];
+        util::assert_permutations_resolve_to(goals, y, vec![]);
+        let goals: Vec<Goal<I32>> = vec![checked_div(x, 2, 3), unify(x, 7), unify(y, 1)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(7, 1)]);
+    }
+
+    #[test]
+    fn overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![checked_div(i32::MIN, -1, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,131 @@
+use canrun::goal::{unify, Goal};
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, TwoOfThree, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use num_traits::{CheckedDiv, CheckedMul, CheckedRem, Zero};
+use std::fmt::Debug;
+
+/// Multiply two values together, failing instead of overflowing.
+///
+/// Like [`mul`](crate::mul), but any direction that would overflow fails
+/// the goal. Deriving a factor from the product requires exact division, so
+/// an uneven product also fails. A zero factor with a zero product leaves the
+/// other factor unbound.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::checked_mul;
+///
+/// let x = var();
+/// let goal: Goal<I32> = checked_mul(i32::MAX, 2, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![]);
+/// ```
+pub fn checked_mul<'a, T, A, B, C, D>(a: A, b: B, c: C) -> Goal<'a, D>
+where
+    T: CheckedMul + CheckedDiv + CheckedRem + Zero + UnifyIn<'a, D> + Debug + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    C: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    Goal::constraint(CheckedProduct {
+        a: a.into_val(),
+        b: b.into_val(),
+        c: c.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct CheckedProduct<T: Debug> {
+    a: Val<T>,
+    b: Val<T>,
+    c: Val<T>,
+}
+
+impl<'a, T, D> Constraint<'a, D> for CheckedProduct<T>
+where
+    T: CheckedMul + CheckedDiv + CheckedRem + Zero + UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, T>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let goal = match TwoOfThree::resolve(&self.a, &self.b, &self.c, state)? {
+            TwoOfThree::AB(a, b, c) => match a.checked_mul(&b) {
+                Some(product) => unify(c, product),
+                None => Goal::fail(),
+            },
+            TwoOfThree::BC(a, b, c) => factor(a, &c, &b),
+            TwoOfThree::AC(a, b, c) => factor(b, &c, &a),
+        };
+        Ok(Box::new(|state| goal.apply(state)))
+    }
+}
+
+/// Relate the missing factor of a product to the one that is known.
+fn factor<'a, T, D>(missing: Val<T>, product: &T, known: &T) -> Goal<'a, D>
+where
+    T: CheckedDiv + CheckedRem + Zero + UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, T>,
+{
+    if known.is_zero() {
+        if product.is_zero() {
+            Goal::succeed()
+        } else {
+            Goal::fail()
+        }
+    } else {
+        match (product.checked_rem(known), product.checked_div(known)) {
+            (Some(rem), Some(missing_value)) if rem.is_zero() => unify(missing, missing_value),
+            _ => Goal::fail(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::checked_mul;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> =
+            vec![unify(x, 2), unify(y, 3), unify(z, 6), checked_mul(x, y, z)];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![(2, 3, 6)]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> =
+            vec![unify(x, 2), unify(y, 3), unify(z, 7), checked_mul(x, y, z)];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![]);
+    }
+
+    #[test]
+    fn zero_factor_leaves_other_open() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![checked_mul(0, x, 0), unify(x, 5)];
+        util::assert_permutations_resolve_to(goals, x, vec![5]);
+    }
+
+    #[test]
+    fn zero_factor_with_non_zero_product_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![checked_mul(x, 0, 3)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn derived_factor_overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![checked_mul(x, -1, i32::MIN)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
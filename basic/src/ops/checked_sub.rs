@@ -0,0 +1,67 @@
+use canrun::goal::Goal;
+use canrun::map_2_opt;
+use canrun::value::IntoVal;
+use canrun::{DomainType, UnifyIn};
+use num_traits::{CheckedAdd, CheckedSub};
+use std::fmt::Debug;
+
+/// Subtract one value from another, failing instead of overflowing.
+///
+/// Like [`sub`](crate::sub), but any direction that would overflow fails
+/// the goal.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::checked_sub;
+///
+/// let x = var();
+/// let goal: Goal<I32> = checked_sub(i32::MIN, 1, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![]);
+/// ```
+pub fn checked_sub<'a, T, A, B, C, D>(a: A, b: B, c: C) -> Goal<'a, D>
+where
+    T: CheckedAdd + CheckedSub + UnifyIn<'a, D> + Debug + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    C: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    map_2_opt(
+        a,
+        b,
+        c,
+        |a, b| a.checked_sub(b),
+        |a, c| a.checked_sub(c),
+        |b, c| b.checked_add(c),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::checked_sub;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> =
+            vec![unify(x, 3), unify(y, 2), unify(z, 1), checked_sub(x, y, z)];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![(3, 2, 1)]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> = vec![
+            unify(x, i32::MIN),
+            unify(y, 1),
+            unify(z, i32::MAX),
+            checked_sub(x, y, z),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![]);
+    }
+}
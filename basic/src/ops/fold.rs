@@ -0,0 +1,63 @@
+use crate::items::partition_items;
+use canrun::goal::{unify, Goal};
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::Val;
+use canrun::{DomainType, UnifyIn};
+use canrun_collections::lvec::LVec;
+use std::fmt::{self, Debug};
+
+/// What a single missing item must be for a fold to reach its total.
+pub(super) enum Missing<T> {
+    Is(T),
+    Any,
+    Fails,
+}
+
+/// Combine the items of an [`LVec`] into a total, or solve for the last
+/// unresolved item once the total is known. Combining returns `None` on
+/// overflow, which fails the goal.
+pub(super) struct Fold<T: Debug> {
+    pub(super) items: Val<LVec<T>>,
+    pub(super) total: Val<T>,
+    pub(super) identity: T,
+    pub(super) combine: fn(T, T) -> Option<T>,
+    pub(super) missing: fn(T, T) -> Missing<T>,
+}
+
+impl<T: Debug> Debug for Fold<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fold {:?} {:?}", self.items, self.total)
+    }
+}
+
+impl<'a, T, D> Constraint<'a, D> for Fold<T>
+where
+    T: UnifyIn<'a, D> + Debug + Copy + 'a,
+    D: DomainType<'a, T> + DomainType<'a, LVec<T>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let items = resolve_1(&self.items, state)?;
+        let (resolved, vars) = partition_items(&items, state);
+        let known = resolved
+            .iter()
+            .try_fold(self.identity, |total, item| (self.combine)(total, **item));
+        let known = match known {
+            Some(known) => known,
+            None => return Ok(Box::new(|_| None)),
+        };
+        let goal = match (vars.as_slice(), state.resolve_val(&self.total)) {
+            ([], _) => unify(self.total.clone(), known),
+            ([var], Val::Resolved(total)) => match (self.missing)(**total, known) {
+                Missing::Is(value) => unify(*var, value),
+                Missing::Any => Goal::succeed(),
+                Missing::Fails => Goal::fail(),
+            },
+            ([var, ..], Val::Var(total)) => return Err(VarWatch::two(*var, *total)),
+            ([a, b, ..], Val::Resolved(_)) => return Err(VarWatch::two(*a, *b)),
+        };
+        Ok(Box::new(|state| goal.apply(state)))
+    }
+}
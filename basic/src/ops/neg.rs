@@ -0,0 +1,68 @@
+use canrun::goal::Goal;
+use canrun::map_1_opt;
+use canrun::value::IntoVal;
+use canrun::{DomainType, UnifyIn};
+use num_traits::CheckedNeg;
+use std::fmt::Debug;
+
+/// Relate a value to its negation.
+///
+/// Values without a negation, such as `i32::MIN`, fail the goal.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::neg;
+///
+/// let x = var();
+/// let goal: Goal<I32> = neg(x, 3);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![-3]);
+/// ```
+pub fn neg<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: CheckedNeg + UnifyIn<'a, D> + Debug + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    map_1_opt(a, b, |a| a.checked_neg(), |b| b.checked_neg())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::neg;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, 2), unify(y, -2), neg(x, y)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(2, -2)]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, 2), unify(y, 2), neg(x, y)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![]);
+    }
+
+    #[test]
+    fn overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![neg(i32::MIN, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+        let goals: Vec<Goal<I32>> = vec![neg(x, i32::MIN)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn reaches_edges() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![neg(x, i32::MAX)];
+        util::assert_permutations_resolve_to(goals, x, vec![-i32::MAX]);
+    }
+}
@@ -0,0 +1,234 @@
+use canrun::goal::{unify, Goal};
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use num_traits::{checked_pow, PrimInt};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+/// Raise an integer to a non-negative integer power.
+///
+/// This works in every direction:
+/// - Knowing the base and exponent gives the result. Overflow fails.
+/// - Knowing the exponent and result finds the integer roots. Even powers of
+///   signed types fork for both the positive and negative root.
+/// - Knowing the base and result finds the exponent. If there are many
+///   possible exponents (e.g. a base of `1`), the goal waits for the
+///   exponent instead.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::pow;
+///
+/// let x = var();
+/// let goal: Goal<I32> = pow(x, 2, 9);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![3, -3]);
+/// ```
+pub fn pow<'a, T, A, B, C, D>(base: A, exp: B, result: C) -> Goal<'a, D>
+where
+    T: PrimInt + UnifyIn<'a, D> + Debug + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    C: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    Goal::constraint(Pow {
+        base: base.into_val(),
+        exp: exp.into_val(),
+        result: result.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Pow<T: Debug> {
+    base: Val<T>,
+    exp: Val<T>,
+    result: Val<T>,
+}
+
+impl<'a, T, D> Constraint<'a, D> for Pow<T>
+where
+    T: PrimInt + UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, T>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        // Resolved by hand rather than with `TwoOfThree`, so that waiting on
+        // an ambiguous exponent can name its var directly.
+        let base = state.resolve_val(&self.base);
+        let exp = state.resolve_val(&self.exp);
+        let result = state.resolve_val(&self.result);
+        let goal = match (base, exp, result) {
+            (Val::Resolved(base), Val::Resolved(exp), result) => {
+                match exp.to_usize().and_then(|exp| checked_pow(**base, exp)) {
+                    Some(value) => unify(result.clone(), value),
+                    None => Goal::fail(),
+                }
+            }
+            (base, Val::Resolved(exp), Val::Resolved(result)) => match exp.to_usize() {
+                None => Goal::fail(),
+                Some(0) if result.is_one() => Goal::succeed(),
+                Some(0) => Goal::fail(),
+                Some(exp) => {
+                    let roots = roots(**result, exp);
+                    Goal::any(roots.into_iter().map(|root| unify(base.clone(), root)))
+                }
+            },
+            (Val::Resolved(base), Val::Var(exp), Val::Resolved(result)) => {
+                match exponent(**base, **result) {
+                    Exponent::Is(value) => unify(*exp, value),
+                    Exponent::Fails => Goal::fail(),
+                    Exponent::Many => return Err(VarWatch::one(*exp)),
+                }
+            }
+            (Val::Var(a), Val::Var(b), _) => return Err(VarWatch::two(*a, *b)),
+            (Val::Var(a), _, Val::Var(c)) => return Err(VarWatch::two(*a, *c)),
+            (_, Val::Var(b), Val::Var(c)) => return Err(VarWatch::two(*b, *c)),
+        };
+        Ok(Box::new(|state| goal.apply(state)))
+    }
+}
+
+/// Find every integer `r` where `r.pow(exp) == value`.
+// `is_multiple_of` would raise the minimum supported Rust version to 1.87
+#[allow(clippy::manual_is_multiple_of)]
+fn roots<T: PrimInt>(value: T, exp: usize) -> Vec<T> {
+    let even = exp % 2 == 0;
+    if even && value < T::zero() {
+        return vec![];
+    }
+    match root(value, exp) {
+        Some(root) if even && root > T::zero() && T::min_value() < T::zero() => {
+            vec![root, T::zero() - root]
+        }
+        Some(root) => vec![root],
+        None => vec![],
+    }
+}
+
+/// Binary search for the root between zero and the value, where raising to
+/// the power is monotonic. Overflow means the candidate is too far from zero.
+fn root<T: PrimInt>(value: T, exp: usize) -> Option<T> {
+    let (mut low, mut high) = if value < T::zero() {
+        (value, T::zero())
+    } else {
+        (T::zero(), value)
+    };
+    while low <= high {
+        // Average without overflowing, rounding towards negative infinity
+        let mid = (low >> 1) + (high >> 1) + (low & high & T::one());
+        let order = match checked_pow(mid, exp) {
+            Some(power) => power.cmp(&value),
+            None if mid > T::zero() => Ordering::Greater,
+            None => Ordering::Less,
+        };
+        match order {
+            Ordering::Equal => return Some(mid),
+            Ordering::Less => low = mid + T::one(),
+            Ordering::Greater => high = mid - T::one(),
+        }
+    }
+    None
+}
+
+enum Exponent<T> {
+    Is(T),
+    Fails,
+    Many,
+}
+
+/// Find the exponent that raises the base to the result.
+fn exponent<T: PrimInt>(base: T, result: T) -> Exponent<T> {
+    let negative_one = T::min_value() < T::zero() && base == T::zero() - T::one();
+    if base.is_zero() && result.is_zero()
+        || base.is_one() && result.is_one()
+        || negative_one && (result.is_one() || result == base)
+    {
+        return Exponent::Many;
+    }
+    if negative_one {
+        return Exponent::Fails;
+    }
+    let mut power = T::one();
+    let mut exp = T::zero();
+    loop {
+        if power == result {
+            return Exponent::Is(exp);
+        }
+        match power.checked_mul(&base) {
+            Some(next) if next != power => power = next,
+            _ => return Exponent::Fails,
+        }
+        exp = exp + T::one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pow, roots};
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn forwards() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![pow(-2, 3, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![-8]);
+    }
+
+    #[test]
+    fn overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![pow(2, 31, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn odd_root() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![pow(x, 3, -27)];
+        util::assert_permutations_resolve_to(goals, x, vec![-3]);
+    }
+
+    #[test]
+    fn inexact_root_fails() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![pow(x, 2, 10)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn finds_exponent() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![pow(3, x, 81)];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+    }
+
+    #[test]
+    fn impossible_exponent() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![pow(-1, x, 2)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn waits_for_ambiguous_exponent() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![pow(-1, x, 1), unify(x, 4)];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+    }
+
+    #[test]
+    fn extreme_roots() {
+        assert_eq!(roots(i32::MAX, 1), vec![i32::MAX]);
+        assert_eq!(roots(i32::MIN, 1), vec![i32::MIN]);
+        assert_eq!(roots(i32::MIN, 31), vec![-2]);
+        assert_eq!(roots(0, 4), vec![0]);
+        assert_eq!(roots(16_u8, 2), vec![4]);
+    }
+}
@@ -0,0 +1,102 @@
+use super::fold::{Fold, Missing};
+use canrun::goal::Goal;
+use canrun::value::IntoVal;
+use canrun::{DomainType, UnifyIn};
+use canrun_collections::lvec::LVec;
+use num_traits::{CheckedDiv, CheckedMul, CheckedRem, One, Zero};
+use std::fmt::Debug;
+
+/// Multiply together the items of an [`LVec`].
+///
+/// Once the total is known, a single unresolved item will be derived from the
+/// others. Division must be exact, so integers that do not divide evenly
+/// fail, as does overflowing in either direction.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::product;
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = product(lvec![2, x, 3], 30);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![5]);
+/// ```
+pub fn product<'a, T, I, P, D>(items: I, total: P) -> Goal<'a, D>
+where
+    T: One + Zero + CheckedMul + CheckedDiv + CheckedRem + UnifyIn<'a, D> + Debug + Copy + 'a,
+    I: IntoVal<LVec<T>>,
+    P: IntoVal<T>,
+    D: DomainType<'a, T> + DomainType<'a, LVec<T>>,
+{
+    Goal::constraint(Fold {
+        items: items.into_val(),
+        total: total.into_val(),
+        identity: T::one(),
+        combine: |a, b| a.checked_mul(&b),
+        missing: |total, known| {
+            if known.is_zero() {
+                if total.is_zero() {
+                    Missing::Any
+                } else {
+                    Missing::Fails
+                }
+            } else {
+                match (total.checked_rem(&known), total.checked_div(&known)) {
+                    (Some(rem), Some(missing)) if rem.is_zero() => Missing::Is(missing),
+                    _ => Missing::Fails,
+                }
+            }
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::product;
+    use canrun::{unify, util, var, Goal};
+    use canrun_collections::{example::Collections, lvec};
+
+    #[test]
+    fn forwards() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(x, 2), product(lvec![4, x, 3], y)];
+        util::assert_permutations_resolve_to(goals, y, vec![24]);
+    }
+
+    #[test]
+    fn inexact_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![product(lvec![x, 4], 10)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn zero_leaves_item_open() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![product(lvec![x, 0], 0), unify(y, 1)];
+        util::assert_permutations_resolve_to(goals, y, vec![1]);
+    }
+
+    #[test]
+    fn overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![product(lvec![i32::MAX, 2], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn missing_item_overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![product(lvec![x, -1], i32::MIN)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn reaches_edges() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![product(lvec![x, -1], i32::MIN + 1)];
+        util::assert_permutations_resolve_to(goals, x, vec![i32::MAX]);
+    }
+}
@@ -0,0 +1,66 @@
+use canrun::goal::project::project_2;
+use canrun::goal::{unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use num_traits::CheckedRem;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Get the remainder of dividing one value by another.
+///
+/// This only works forwards, since many values share the same remainder. A
+/// zero divisor or an overflowing division fails instead of panicking.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::rem;
+///
+/// let x = var();
+/// let goal: Goal<I32> = rem(7, 3, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![1]);
+/// ```
+pub fn rem<'a, T, A, B, C, D>(a: A, b: B, c: C) -> Goal<'a, D>
+where
+    T: CheckedRem + UnifyIn<'a, D> + Debug + Copy + 'a,
+    A: IntoVal<T>,
+    B: IntoVal<T>,
+    C: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    let c: Val<T> = c.into_val();
+    project_2(a, b, move |a: Rc<T>, b: Rc<T>| match a.checked_rem(&b) {
+        Some(rem) => unify(c.clone(), rem),
+        None => Goal::fail(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rem;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y, z) = (var(), var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, -7), unify(y, 3), unify(z, -1), rem(x, y, z)];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![(-7, 3, -1)]);
+    }
+
+    #[test]
+    fn fails_on_zero_divisor() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, 7), unify(y, 0), rem(x, y, var())];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![]);
+    }
+
+    #[test]
+    fn fails_on_overflow() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![rem(i32::MIN, -1, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,52 @@
+use canrun::goal::project::project_1;
+use canrun::goal::{unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use num_traits::Signed;
+
+/// Get the sign of a value according to [`Signed::signum`].
+///
+/// For integers this is `-1`, `0` or `1`. This only works forwards.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, util, var, Goal};
+/// use canrun::domains::example::I32;
+/// use canrun_basic::sign;
+///
+/// let x = var();
+/// let goal: Goal<I32> = sign(-5, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![-1]);
+/// ```
+pub fn sign<'a, T, A, S, D>(a: A, s: S) -> Goal<'a, D>
+where
+    T: Signed + UnifyIn<'a, D> + 'a,
+    A: IntoVal<T>,
+    S: IntoVal<T>,
+    D: DomainType<'a, T>,
+{
+    let s: Val<T> = s.into_val();
+    project_1(a, move |a: &T| unify(s.clone(), a.signum()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign;
+    use canrun::domains::example::I32;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, 0), unify(y, 0), sign(x, y)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn fails() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<I32>> = vec![unify(x, 3), unify(y, -1), sign(x, y)];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![]);
+    }
+}
@@ -0,0 +1,91 @@
+use super::fold::{Fold, Missing};
+use canrun::goal::Goal;
+use canrun::value::IntoVal;
+use canrun::{DomainType, UnifyIn};
+use canrun_collections::lvec::LVec;
+use num_traits::{CheckedAdd, CheckedSub, Zero};
+use std::fmt::Debug;
+
+/// Add up the items of an [`LVec`].
+///
+/// Once the total is known, a single unresolved item will be derived from the
+/// others. Overflowing in either direction fails the goal.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::sum;
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = sum(lvec![1, x, 3], 10);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![6]);
+/// ```
+pub fn sum<'a, T, I, S, D>(items: I, total: S) -> Goal<'a, D>
+where
+    T: Zero + CheckedAdd + CheckedSub + UnifyIn<'a, D> + Debug + Copy + 'a,
+    I: IntoVal<LVec<T>>,
+    S: IntoVal<T>,
+    D: DomainType<'a, T> + DomainType<'a, LVec<T>>,
+{
+    Goal::constraint(Fold {
+        items: items.into_val(),
+        total: total.into_val(),
+        identity: T::zero(),
+        combine: |a, b| a.checked_add(&b),
+        missing: |total, known| match total.checked_sub(&known) {
+            Some(missing) => Missing::Is(missing),
+            None => Missing::Fails,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sum;
+    use canrun::{unify, util, var, Goal};
+    use canrun_collections::{example::Collections, lvec};
+
+    #[test]
+    fn forwards() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(x, 2), sum(lvec![1, x, 3], y)];
+        util::assert_permutations_resolve_to(goals, y, vec![6]);
+    }
+
+    #[test]
+    fn empty() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![sum(lvec![], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![0]);
+    }
+
+    #[test]
+    fn derives_missing_item() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(y, 4), sum(lvec![x, y], 10)];
+        util::assert_permutations_resolve_to(goals, x, vec![6]);
+    }
+
+    #[test]
+    fn overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![sum(lvec![i32::MAX, 1], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn missing_item_overflow_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![sum(lvec![x, -1], i32::MAX)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn reaches_edges() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![sum(lvec![i32::MIN, x], -1)];
+        util::assert_permutations_resolve_to(goals, x, vec![i32::MAX]);
+    }
+}
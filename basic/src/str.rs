@@ -0,0 +1,24 @@
+//! String goals including [`concat`](concat::concat),
+//! [`length`](length::length), [`starts_with`](affix::starts_with),
+//! [`ends_with`](affix::ends_with), [`contains`](contains::contains),
+//! [`to_upper`](case::to_upper), [`to_lower`](case::to_lower),
+//! [`chars`](chars::chars) and the regex based [`matches`](pattern::matches)
+//! and [`captures`](pattern::captures).
+//!
+//! These work with [`String`] values.
+
+mod affix;
+mod case;
+mod chars;
+mod concat;
+mod contains;
+mod length;
+mod pattern;
+
+pub use affix::{ends_with, starts_with};
+pub use case::{to_lower, to_upper};
+pub use chars::chars;
+pub use concat::concat;
+pub use contains::contains;
+pub use length::length;
+pub use pattern::{captures, matches};
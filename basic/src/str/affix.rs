@@ -0,0 +1,81 @@
+use super::concat;
+use canrun::goal::Goal;
+use canrun::value::{var, IntoVal, LVar};
+use canrun::DomainType;
+
+/// Succeed if a string starts with a prefix.
+///
+/// If only the string is known, every prefix is generated.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::starts_with;
+///
+/// let x = var();
+/// let goal: Goal<Strings> = starts_with("ab".to_string(), x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec!["", "a", "ab"]);
+/// ```
+pub fn starts_with<'a, S, P, D>(string: S, prefix: P) -> Goal<'a, D>
+where
+    S: IntoVal<String>,
+    P: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    let rest: LVar<String> = var();
+    concat(prefix, rest, string)
+}
+
+/// Succeed if a string ends with a suffix.
+///
+/// If only the string is known, every suffix is generated.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::ends_with;
+///
+/// let x = var();
+/// let goal: Goal<Strings> = ends_with("ab".to_string(), x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec!["ab", "b", ""]);
+/// ```
+pub fn ends_with<'a, S, X, D>(string: S, suffix: X) -> Goal<'a, D>
+where
+    S: IntoVal<String>,
+    X: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    let rest: LVar<String> = var();
+    concat(rest, suffix, string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ends_with, starts_with};
+    use crate::example::Strings;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn starts_with_known() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "foobar".to_string()),
+            starts_with(x, "foo".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec!["foobar".to_string()]);
+    }
+
+    #[test]
+    fn ends_with_fails() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "foobar".to_string()),
+            ends_with(x, "foo".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,78 @@
+use canrun::goal::project::project_1;
+use canrun::goal::{unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::DomainType;
+
+/// Relate a string to its uppercase equivalent.
+///
+/// This only works forwards, since many strings share the same uppercase
+/// form. The goal waits for the original string to be resolved.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::to_upper;
+///
+/// let x = var();
+/// let goal: Goal<Strings> = to_upper("abc".to_string(), x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec!["ABC"]);
+/// ```
+pub fn to_upper<'a, S, U, D>(string: S, upper: U) -> Goal<'a, D>
+where
+    S: IntoVal<String>,
+    U: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    let upper: Val<String> = upper.into_val();
+    project_1(string, move |s: &String| {
+        unify(upper.clone(), s.to_uppercase())
+    })
+}
+
+/// Relate a string to its lowercase equivalent.
+///
+/// Like [`to_upper`], this only works forwards.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::to_lower;
+///
+/// let x = var();
+/// let goal: Goal<Strings> = to_lower("ABC".to_string(), x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec!["abc"]);
+/// ```
+pub fn to_lower<'a, S, L, D>(string: S, lower: L) -> Goal<'a, D>
+where
+    S: IntoVal<String>,
+    L: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    let lower: Val<String> = lower.into_val();
+    project_1(string, move |s: &String| {
+        unify(lower.clone(), s.to_lowercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_lower, to_upper};
+    use crate::example::Strings;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn case_insensitive_match() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "Foo".to_string()),
+            unify(y, "fOO".to_string()),
+            to_lower(x, "foo".to_string()),
+            to_upper(y, "FOO".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec!["Foo".to_string()]);
+    }
+}
@@ -0,0 +1,97 @@
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{Constraint, OneOfTwo, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use canrun_collections::lvec::LVec;
+
+/// Relate a string to an [`LVec`] of its characters.
+///
+/// This works in either direction. When converting from an `LVec`, the goal
+/// waits for every character to be resolved.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::chars;
+/// use canrun_collections::lvec;
+///
+/// let (x, y) = (var(), var());
+/// let goal: Goal<Strings> = canrun::all![
+///     chars("hi".to_string(), lvec![x, 'i']),
+///     chars(y, lvec!['o', x]),
+/// ];
+/// let results: Vec<_> = goal.query((x, y)).collect();
+/// assert_eq!(results, vec![('h', "oh".to_string())]);
+/// ```
+pub fn chars<'a, S, C, D>(string: S, chars: C) -> Goal<'a, D>
+where
+    S: IntoVal<String>,
+    C: IntoVal<LVec<char>>,
+    LVec<char>: UnifyIn<'a, D>,
+    D: DomainType<'a, String> + DomainType<'a, char> + DomainType<'a, LVec<char>>,
+{
+    Goal::constraint(Chars {
+        string: string.into_val(),
+        chars: chars.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Chars {
+    string: Val<String>,
+    chars: Val<LVec<char>>,
+}
+
+impl<'a, D> Constraint<'a, D> for Chars
+where
+    LVec<char>: UnifyIn<'a, D>,
+    D: DomainType<'a, String> + DomainType<'a, char> + DomainType<'a, LVec<char>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        match OneOfTwo::resolve(&self.string, &self.chars, state)? {
+            OneOfTwo::A(string, chars) => {
+                let found: LVec<char> = string.chars().into();
+                Ok(Box::new(move |state| {
+                    state.unify(&found.into_val(), &chars)
+                }))
+            }
+            OneOfTwo::B(string, chars) => {
+                let joined = chars
+                    .iter()
+                    .map(|c| match state.resolve_val(c) {
+                        Val::Resolved(c) => Ok(**c),
+                        Val::Var(var) => Err(VarWatch::one(*var)),
+                    })
+                    .collect::<Result<String, _>>()?
+                    .into_val();
+                Ok(Box::new(move |state| state.unify(&joined, &string)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chars;
+    use crate::example::Strings;
+    use canrun::{unify, util, var, Goal};
+    use canrun_collections::lvec;
+
+    #[test]
+    fn string_to_chars() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![chars("ab".to_string(), x)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec!['a', 'b']]);
+    }
+
+    #[test]
+    fn chars_to_string_waits_for_chars() {
+        let (c, s) = (var(), var());
+        let goals: Vec<Goal<Strings>> = vec![chars(s, lvec!['a', c]), unify(c, 'z')];
+        util::assert_permutations_resolve_to(goals, s, vec!["az".to_string()]);
+    }
+}
@@ -0,0 +1,145 @@
+use canrun::goal::{all, unify, Goal};
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::DomainType;
+use std::rc::Rc;
+
+/// Relate two strings to the result of joining them together.
+///
+/// When only the joined string is known, the state is forked for each way of
+/// splitting it in two (between characters).
+///
+/// # Examples:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::concat;
+///
+/// let x = var();
+/// let goal: Goal<Strings> = concat("foo".to_string(), "bar".to_string(), x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec!["foobar"]);
+/// ```
+///
+/// ```
+/// # use canrun::{var, Goal};
+/// # use canrun_basic::example::Strings;
+/// # use canrun_basic::str::concat;
+/// let (x, y) = (var(), var());
+/// let goal: Goal<Strings> = concat(x, y, "ab".to_string());
+/// let results: Vec<(String, String)> = goal.query((x, y)).collect();
+/// assert_eq!(results, vec![
+///     ("".into(), "ab".into()),
+///     ("a".into(), "b".into()),
+///     ("ab".into(), "".into()),
+/// ]);
+/// ```
+pub fn concat<'a, A, B, AB, D>(a: A, b: B, ab: AB) -> Goal<'a, D>
+where
+    A: IntoVal<String>,
+    B: IntoVal<String>,
+    AB: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    Goal::constraint(Concat {
+        a: a.into_val(),
+        b: b.into_val(),
+        ab: ab.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Concat {
+    a: Val<String>,
+    b: Val<String>,
+    ab: Val<String>,
+}
+
+impl<'a, D> Constraint<'a, D> for Concat
+where
+    D: DomainType<'a, String>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        use canrun::value::Val::{Resolved, Var};
+        let a = state.resolve_val(&self.a).clone();
+        let b = state.resolve_val(&self.b).clone();
+        let ab = state.resolve_val(&self.ab).clone();
+        match (&a, &b, &ab) {
+            (Resolved(a), Resolved(b), _) => {
+                let joined = format!("{}{}", a, b).into_val();
+                Ok(Box::new(move |state| state.unify(&joined, &ab)))
+            }
+            (Resolved(a), _, Resolved(whole)) => {
+                let rest = whole.strip_prefix(a.as_str()).map(String::from);
+                Ok(Box::new(move |state| state.unify(&rest?.into_val(), &b)))
+            }
+            (_, Resolved(b), Resolved(whole)) => {
+                let rest = whole.strip_suffix(b.as_str()).map(String::from);
+                Ok(Box::new(move |state| state.unify(&rest?.into_val(), &a)))
+            }
+            (_, _, Resolved(whole)) => Ok(split(a, b, whole.clone())),
+            (Var(a), _, Var(ab)) => Err(VarWatch::two(*a, *ab)),
+            (_, Var(b), Var(ab)) => Err(VarWatch::two(*b, *ab)),
+        }
+    }
+}
+
+fn split<'a, D>(a: Val<String>, b: Val<String>, whole: Rc<String>) -> ResolveFn<'a, D>
+where
+    D: DomainType<'a, String>,
+{
+    let goals: Vec<Goal<D>> = whole
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(whole.len()))
+        .map(|i| {
+            all![
+                unify::<String, _, _, D>(a.clone(), whole[..i].to_string()),
+                unify::<String, _, _, D>(b.clone(), whole[i..].to_string()),
+            ]
+        })
+        .collect();
+    Box::new(|state| Goal::any(goals).apply(state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::concat;
+    use crate::example::Strings;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn joins() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "ab".to_string()),
+            concat(x, "c".to_string(), "abc".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec!["ab".to_string()]);
+    }
+
+    #[test]
+    fn finds_suffix() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![concat("a".to_string(), x, "abc".to_string())];
+        util::assert_permutations_resolve_to(goals, x, vec!["bc".to_string()]);
+    }
+
+    #[test]
+    fn mismatched_prefix() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![concat("b".to_string(), x, "abc".to_string())];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn splits_between_characters() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Strings>> =
+            vec![concat(x, y, "é!".to_string()), unify(y, "!".to_string())];
+        util::assert_permutations_resolve_to(goals, x, vec!["é".to_string()]);
+    }
+}
@@ -0,0 +1,104 @@
+use canrun::goal::{unify, Goal};
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::DomainType;
+use std::collections::HashSet;
+
+/// Succeed if a string contains a substring.
+///
+/// The goal waits for the string to be resolved. If the substring is not
+/// known, the state is forked for each distinct substring, shortest first.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::contains;
+///
+/// let x = var();
+/// let goal: Goal<Strings> = contains("aba".to_string(), x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec!["", "a", "b", "ab", "ba", "aba"]);
+/// ```
+pub fn contains<'a, S, X, D>(string: S, substring: X) -> Goal<'a, D>
+where
+    S: IntoVal<String>,
+    X: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    Goal::constraint(Contains {
+        string: string.into_val(),
+        substring: substring.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Contains {
+    string: Val<String>,
+    substring: Val<String>,
+}
+
+impl<'a, D> Constraint<'a, D> for Contains
+where
+    D: DomainType<'a, String>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let string = resolve_1(&self.string, state)?;
+        match state.resolve_val(&self.substring) {
+            Val::Resolved(substring) => {
+                let found = string.contains(substring.as_str());
+                Ok(Box::new(
+                    move |state| if found { Some(state) } else { None },
+                ))
+            }
+            Val::Var(_) => {
+                let bounds: Vec<usize> = string
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain(std::iter::once(string.len()))
+                    .collect();
+                let mut seen = HashSet::new();
+                let mut goals: Vec<Goal<D>> = Vec::new();
+                for width in 0..bounds.len() {
+                    for start in 0..bounds.len() - width {
+                        let substring = &string[bounds[start]..bounds[start + width]];
+                        if seen.insert(substring) {
+                            goals.push(unify(self.substring.clone(), substring.to_string()));
+                        }
+                    }
+                }
+                Ok(Box::new(|state| Goal::any(goals).apply(state)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::contains;
+    use crate::example::Strings;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn contains_substring() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "foobar".to_string()),
+            contains(x, "oba".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec!["foobar".to_string()]);
+    }
+
+    #[test]
+    fn missing_substring() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "foobar".to_string()),
+            contains(x, "baz".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
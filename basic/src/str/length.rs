@@ -0,0 +1,52 @@
+use canrun::goal::project::project_1;
+use canrun::goal::{unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::DomainType;
+
+/// Relate a string to its length in characters.
+///
+/// This only works forwards, so the goal waits for the string to be resolved.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::length;
+///
+/// let n = var();
+/// let goal: Goal<Strings> = length("héllo".to_string(), n);
+/// let results: Vec<_> = goal.query(n).collect();
+/// assert_eq!(results, vec![5]);
+/// ```
+pub fn length<'a, S, N, D>(string: S, len: N) -> Goal<'a, D>
+where
+    S: IntoVal<String>,
+    N: IntoVal<usize>,
+    D: DomainType<'a, String> + DomainType<'a, usize>,
+{
+    let len: Val<usize> = len.into_val();
+    project_1(string, move |s: &String| {
+        unify(len.clone(), s.chars().count())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::length;
+    use crate::example::Strings;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn counts_characters() {
+        let (x, n) = (var(), var());
+        let goals: Vec<Goal<Strings>> = vec![unify(x, "abc".to_string()), length(x, n)];
+        util::assert_permutations_resolve_to(goals, n, vec![3]);
+    }
+
+    #[test]
+    fn wrong_length() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![unify(x, "abc".to_string()), length(x, 2)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,352 @@
+use canrun::goal::{unify, Goal};
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::DomainType;
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use std::collections::HashSet;
+
+/// The most candidate strings a pattern may expand to before it is treated as
+/// unbounded.
+const MAX_CANDIDATES: usize = 1000;
+
+/// Succeed if the entire string matches a regex pattern.
+///
+/// Unlike [`Regex::is_match`], the pattern is anchored at both ends. If the
+/// string is not resolved and the pattern only describes a small finite set
+/// of strings, the state is forked for each of them. Otherwise the goal
+/// waits for the string to be resolved, much like
+/// [`assert_1`](canrun::goal::project::assert_1).
+///
+/// The pattern is taken as a string so that it can be both matched and
+/// enumerated the same way. Options such as case insensitivity must be set
+/// with inline flags like `(?i)` rather than a
+/// [`RegexBuilder`](regex::RegexBuilder). An invalid pattern returns the
+/// [`regex::Error`].
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::matches;
+///
+/// let x = var();
+/// let goal: Goal<Strings> = matches(x, "v[12]").unwrap();
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec!["v1", "v2"]);
+/// ```
+pub fn matches<'a, S, D>(string: S, pattern: &str) -> Result<Goal<'a, D>, regex::Error>
+where
+    S: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    captures(string, pattern, Vec::<Val<String>>::new())
+}
+
+/// Succeed if the entire string matches a regex pattern, binding capture
+/// groups.
+///
+/// Each item in `groups` is unified with the corresponding capture group,
+/// starting from group 1. Groups that do not participate in the match are
+/// left alone. Enumeration and flags work the same as in [`matches`].
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_basic::example::Strings;
+/// use canrun_basic::str::captures;
+///
+/// let (user, host) = (var(), var());
+/// let goal: Goal<Strings> = captures(
+///     "bob@example.com".to_string(),
+///     r"(\w+)@([\w.]+)",
+///     vec![user, host],
+/// )
+/// .unwrap();
+/// let results: Vec<(String, String)> = goal.query((user, host)).collect();
+/// assert_eq!(results, vec![("bob".into(), "example.com".into())]);
+/// ```
+pub fn captures<'a, S, G, D>(
+    string: S,
+    pattern: &str,
+    groups: Vec<G>,
+) -> Result<Goal<'a, D>, regex::Error>
+where
+    S: IntoVal<String>,
+    G: IntoVal<String>,
+    D: DomainType<'a, String>,
+{
+    // Check the pattern on its own first, so that it can't close the group
+    // it is wrapped in.
+    Regex::new(pattern)?;
+    let anchored = Regex::new(&format!(r"\A(?:{})\z", pattern))?;
+    Ok(Goal::constraint(Pattern {
+        string: string.into_val(),
+        pattern: pattern.to_string(),
+        anchored,
+        groups: groups.into_iter().map(IntoVal::into_val).collect(),
+    }))
+}
+
+#[derive(Debug)]
+struct Pattern {
+    string: Val<String>,
+    pattern: String,
+    anchored: Regex,
+    groups: Vec<Val<String>>,
+}
+
+impl Pattern {
+    fn bind_groups<'a, D>(&self, string: &str) -> Option<Vec<Goal<'a, D>>>
+    where
+        D: DomainType<'a, String>,
+    {
+        let found = self.anchored.captures(string)?;
+        let goals = self
+            .groups
+            .iter()
+            .enumerate()
+            .filter_map(|(index, group)| {
+                let matched = found.get(index + 1)?.as_str().to_string();
+                Some(unify::<String, _, _, D>(group.clone(), matched))
+            })
+            .collect();
+        Some(goals)
+    }
+}
+
+impl<'a, D> Constraint<'a, D> for Pattern
+where
+    D: DomainType<'a, String>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        match state.resolve_val(&self.string) {
+            Val::Resolved(string) => {
+                let goals = self.bind_groups(string);
+                Ok(Box::new(move |state| Goal::all(goals?).apply(state)))
+            }
+            Val::Var(var) => {
+                let candidates = regex_syntax::parse(&self.pattern)
+                    .ok()
+                    .and_then(|hir| expand(&hir))
+                    .ok_or_else(|| VarWatch::one(*var))?;
+                let goals: Vec<Goal<D>> = candidates
+                    .into_iter()
+                    .filter_map(|candidate| {
+                        let mut goals = self.bind_groups(&candidate)?;
+                        goals.push(unify(self.string.clone(), candidate));
+                        Some(Goal::all(goals))
+                    })
+                    .collect();
+                Ok(Box::new(|state| Goal::any(goals).apply(state)))
+            }
+        }
+    }
+}
+
+/// Expand a parsed pattern into every string it could match, in order and
+/// without duplicates. Returns `None` for unbounded or overly large patterns.
+///
+/// Look-around assertions are treated as empty, so the results are a superset
+/// to be checked against the real pattern.
+fn expand(hir: &Hir) -> Option<Vec<String>> {
+    let expanded = match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => vec![String::new()],
+        HirKind::Literal(literal) => vec![String::from_utf8(literal.0.to_vec()).ok()?],
+        HirKind::Class(Class::Unicode(class)) => {
+            // Count the chars first, so that huge classes like `.` give up
+            // before allocating anything.
+            let mut count = 0;
+            for range in class.iter() {
+                count += range.end() as usize - range.start() as usize + 1;
+                if count > MAX_CANDIDATES {
+                    return None;
+                }
+            }
+            class
+                .iter()
+                .flat_map(|range| (range.start()..=range.end()).map(String::from))
+                .collect()
+        }
+        HirKind::Class(Class::Bytes(_)) => return None,
+        HirKind::Capture(capture) => expand(&capture.sub)?,
+        HirKind::Repetition(repetition) => {
+            let max = repetition.max?;
+            if repetition.min as usize > MAX_CANDIDATES {
+                return None;
+            }
+            let sub = expand(&repetition.sub)?;
+            if sub.iter().all(String::is_empty) {
+                // Repeating only empty strings can't produce anything new.
+                let matches_empty = repetition.min == 0 || !sub.is_empty();
+                return Some(if matches_empty {
+                    vec![String::new()]
+                } else {
+                    Vec::new()
+                });
+            }
+            let mut expanded = Vec::new();
+            let mut current = vec![String::new()];
+            for count in 0..=max {
+                if count >= repetition.min {
+                    expanded.extend(current.iter().cloned());
+                    if expanded.len() > MAX_CANDIDATES {
+                        return None;
+                    }
+                }
+                if count < max {
+                    current = product(&current, &sub)?;
+                }
+            }
+            expanded
+        }
+        HirKind::Concat(subs) => subs.iter().try_fold(vec![String::new()], |prefixes, sub| {
+            product(&prefixes, &expand(sub)?)
+        })?,
+        HirKind::Alternation(subs) => {
+            let mut expanded = Vec::new();
+            for sub in subs {
+                expanded.extend(expand(sub)?);
+                if expanded.len() > MAX_CANDIDATES {
+                    return None;
+                }
+            }
+            expanded
+        }
+    };
+    if expanded.len() > MAX_CANDIDATES {
+        return None;
+    }
+    let mut seen = HashSet::new();
+    Some(
+        expanded
+            .into_iter()
+            .filter(|s| seen.insert(s.clone()))
+            .collect(),
+    )
+}
+
+fn product(prefixes: &[String], suffixes: &[String]) -> Option<Vec<String>> {
+    if prefixes.len() * suffixes.len() > MAX_CANDIDATES {
+        return None;
+    }
+    Some(
+        prefixes
+            .iter()
+            .flat_map(|prefix| {
+                suffixes
+                    .iter()
+                    .map(move |suffix| format!("{}{}", prefix, suffix))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{captures, expand, matches};
+    use crate::example::Strings;
+    use canrun::{unify, util, var, Goal, LVar};
+
+    #[test]
+    fn requires_full_match() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> =
+            vec![unify(x, "abc".to_string()), matches(x, "a|ab").unwrap()];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn matches_alternative() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> =
+            vec![unify(x, "ab".to_string()), matches(x, "a|ab").unwrap()];
+        util::assert_permutations_resolve_to(goals, x, vec!["ab".to_string()]);
+    }
+
+    #[test]
+    fn binds_capture_groups() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "2020-06".to_string()),
+            captures(x, r"(\d+)-(\d+)", vec![var(), y]).unwrap(),
+        ];
+        util::assert_permutations_resolve_to(goals, y, vec!["06".to_string()]);
+    }
+
+    #[test]
+    fn conflicting_capture_group() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> = vec![
+            unify(x, "2020-06".to_string()),
+            captures(x, r"(\d+)-\d+", vec!["1999".to_string()]).unwrap(),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn enumerates_with_captures() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Strings>> = vec![
+            captures(x, "(a|b)c?", vec![y]).unwrap(),
+            unify(y, "b".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec!["b".to_string(), "bc".to_string()]);
+    }
+
+    #[test]
+    fn waits_on_unbounded_patterns() {
+        let x = var();
+        let goals: Vec<Goal<Strings>> =
+            vec![matches(x, "a+").unwrap(), unify(x, "aaa".to_string())];
+        util::assert_permutations_resolve_to(goals, x, vec!["aaa".to_string()]);
+    }
+
+    #[test]
+    fn inline_flags_apply_everywhere() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Strings>> = vec![
+            captures(x, "(?i)(a)b", vec![y]).unwrap(),
+            unify(y, "A".to_string()),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec!["Ab".to_string(), "AB".to_string()]);
+
+        let goals: Vec<Goal<Strings>> =
+            vec![matches(x, "(?i)ab").unwrap(), unify(x, "aB".to_string())];
+        util::assert_permutations_resolve_to(goals, x, vec!["aB".to_string()]);
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let x: LVar<String> = var();
+        assert!(matches::<_, Strings>(x, "a)|(b").is_err());
+        assert!(matches::<_, Strings>(x, "(").is_err());
+    }
+
+    #[test]
+    fn expands_finite_patterns() {
+        let parse = |pattern| expand(&regex_syntax::parse(pattern).unwrap());
+        assert_eq!(
+            parse("x[a-c]{1,2}"),
+            Some(
+                vec![
+                    "xa", "xb", "xc", "xaa", "xab", "xac", "xba", "xbb", "xbc", "xca", "xcb", "xcc"
+                ]
+                .into_iter()
+                .map(String::from)
+                .collect()
+            )
+        );
+        assert_eq!(parse("a*"), None);
+        assert_eq!(parse("."), None);
+        assert_eq!(parse("[a-z]|."), None);
+        assert_eq!(parse("a{0,20000}"), None);
+        assert_eq!(parse("(?:a|){20000}"), None);
+        assert_eq!(parse("(?:){4000000000}"), Some(vec![String::new()]));
+        assert_eq!(parse("a{1001}"), None);
+        assert_eq!(parse("(?:a|a)b?"), Some(vec!["a".into(), "ab".into()]));
+    }
+}
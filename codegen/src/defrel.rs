@@ -0,0 +1,123 @@
+use crate::derive::is_generic;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{FnArg, ItemFn, Pat, ReturnType, Type};
+
+/// One or more relation functions declared in a single `defrel!` block.
+pub(crate) struct Relations(Vec<ItemFn>);
+
+impl Parse for Relations {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut relations = Vec::new();
+        while !input.is_empty() {
+            relations.push(input.parse()?);
+        }
+        Ok(Relations(relations))
+    }
+}
+
+pub(crate) fn defrel(relations: Relations) -> syn::Result<TokenStream> {
+    relations.0.into_iter().map(relation).collect()
+}
+
+fn relation(item: ItemFn) -> syn::Result<TokenStream> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item;
+    let name = &sig.ident;
+    let name_str = name.to_string();
+
+    let mut idents = Vec::new();
+    let mut types: Vec<&Type> = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Typed(arg) => match &*arg.pat {
+                Pat::Ident(pat) => {
+                    idents.push(&pat.ident);
+                    types.push(&arg.ty);
+                }
+                pat => {
+                    return Err(syn::Error::new(
+                        pat.span(),
+                        "defrel! parameters must be simple identifiers",
+                    ))
+                }
+            },
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "defrel! relations cannot take `self`",
+                ))
+            }
+        }
+    }
+
+    let mut generics = sig.generics.clone();
+    if !generics.lifetimes().any(|l| l.lifetime.ident == "a") {
+        generics.params.insert(0, syn::parse_quote!('a));
+    }
+
+    // Without an explicit return type the relation is generic over any domain
+    // containing the parameter types.
+    let infer_domain = matches!(sig.output, ReturnType::Default);
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => quote!(#ty),
+        ReturnType::Default => {
+            if !generics.type_params().any(|p| p.ident == "D") {
+                generics.params.push(syn::parse_quote!(D));
+            }
+            quote!(canrun::Goal<'a, D>)
+        }
+    };
+
+    let mut distinct_types: Vec<&Type> = Vec::new();
+    for ty in &types {
+        let tokens = quote!(#ty).to_string();
+        if !distinct_types
+            .iter()
+            .any(|t| quote!(#t).to_string() == tokens)
+        {
+            distinct_types.push(ty);
+        }
+    }
+    let where_clause = generics.make_where_clause();
+    if infer_domain {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(D: canrun::Domain<'a>));
+    }
+    for ty in distinct_types {
+        if is_generic(&sig.generics, ty) {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: std::fmt::Debug + 'a));
+        }
+        if infer_domain {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: canrun::UnifyIn<'a, D>));
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(D: canrun::DomainType<'a, #ty>));
+        }
+    }
+    let where_clause = &generics.where_clause;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #name #generics(#(#idents: impl canrun::IntoVal<#types>),*) -> #output
+        #where_clause
+        {
+            #(let #idents: canrun::Val<#types> = canrun::IntoVal::into_val(#idents);)*
+            canrun::goal::lazy_named(#name_str, move || {
+                #(let #idents = #idents.clone();)*
+                #block
+            })
+        }
+    })
+}
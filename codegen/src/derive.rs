@@ -0,0 +1,249 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, GenericArgument, PathArguments, Type};
+
+/// The pieces of a single struct or enum variant needed to destructure and
+/// rebuild it.
+struct Shape {
+    /// `None` for a struct, otherwise the name of the enum variant.
+    variant: Option<syn::Ident>,
+    fields: Fields,
+}
+
+impl Shape {
+    /// Path used to match or construct this shape on `base` (e.g. `Self` or
+    /// `Self::Variant`).
+    fn path(&self, base: &TokenStream) -> TokenStream {
+        match &self.variant {
+            Some(variant) => quote!(#base::#variant),
+            None => base.clone(),
+        }
+    }
+}
+
+fn shapes(input: &DeriveInput) -> syn::Result<Vec<Shape>> {
+    match &input.data {
+        Data::Struct(data) => Ok(vec![Shape {
+            variant: None,
+            fields: data.fields.clone(),
+        }]),
+        Data::Enum(data) => Ok(data
+            .variants
+            .iter()
+            .map(|variant| Shape {
+                variant: Some(variant.ident.clone()),
+                fields: variant.fields.clone(),
+            })
+            .collect()),
+        Data::Union(data) => Err(syn::Error::new(
+            data.union_token.span(),
+            "canrun derives do not support unions",
+        )),
+    }
+}
+
+/// Build a destructuring pattern such as `Self::Variant(a0, a1)` or
+/// `Self { x: a0 }`, binding each field to `{prefix}{n}`.
+fn pattern(path: TokenStream, fields: &Fields, prefix: &str) -> TokenStream {
+    let names = (0..fields.len()).map(|n| format_ident!("{}{}", prefix, n));
+    match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|f| &f.ident);
+            quote!(#path { #(#idents: #names),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#names),*)),
+        Fields::Unit => quote!(#path),
+    }
+}
+
+/// Extract `T` from a field declared as `Val<T>`.
+fn val_inner(ty: &Type) -> Option<&Type> {
+    if let Type::Path(path) = ty {
+        let last = path.path.segments.last()?;
+        if last.ident != "Val" {
+            return None;
+        }
+        if let PathArguments::AngleBracketed(args) = &last.arguments {
+            if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                return Some(inner);
+            }
+        }
+    }
+    None
+}
+
+/// Collect the distinct `T`s wrapped by each `Val<T>` field, skipping
+/// references back to the type being derived. Requiring `Self: UnifyIn` while
+/// implementing it for `Self` would send the trait solver into a loop.
+fn inner_types(input: &DeriveInput, shapes: &[Shape]) -> syn::Result<Vec<Type>> {
+    let mut found: Vec<Type> = Vec::new();
+    for shape in shapes {
+        for field in shape.fields.iter() {
+            let inner = val_inner(&field.ty).ok_or_else(|| {
+                syn::Error::new(
+                    field.ty.span(),
+                    "canrun derives require every field to be a `Val<T>`",
+                )
+            })?;
+            let is_self = match inner {
+                Type::Path(path) => {
+                    path.path.is_ident("Self")
+                        || path.path.segments.last().map(|s| &s.ident) == Some(&input.ident)
+                }
+                _ => false,
+            };
+            // `syn::Type` only implements `PartialEq` with the extra-traits
+            // feature, so compare the tokens instead.
+            let tokens = quote!(#inner).to_string();
+            if !is_self && !found.iter().any(|t| quote!(#t).to_string() == tokens) {
+                found.push(inner.clone());
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Whether a type mentions any of the given generic type parameters.
+///
+/// Bounds such as `f64: ReifyIn<'a, D>` would shadow the concrete impl and
+/// prevent `<f64 as ReifyIn>::Reified` from normalizing, so they are only
+/// emitted for types that actually need them.
+pub(crate) fn is_generic(generics: &syn::Generics, ty: &Type) -> bool {
+    fn mentions(tokens: TokenStream, params: &[&syn::Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => params.contains(&&ident),
+            proc_macro2::TokenTree::Group(group) => mentions(group.stream(), params),
+            _ => false,
+        })
+    }
+    let params: Vec<_> = generics.type_params().map(|p| &p.ident).collect();
+    mentions(quote!(#ty), &params)
+}
+
+pub(crate) fn derive_unify_in(input: DeriveInput) -> syn::Result<TokenStream> {
+    let shapes = shapes(&input)?;
+    let inner = inner_types(&input, &shapes)?;
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, syn::parse_quote!('a));
+    generics.params.push(syn::parse_quote!(D));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|w| &w.predicates);
+
+    let arms = shapes.iter().map(|shape| {
+        let left = pattern(shape.path(&quote!(Self)), &shape.fields, "a");
+        let right = pattern(shape.path(&quote!(Self)), &shape.fields, "b");
+        let unifies = (0..shape.fields.len()).map(|n| {
+            let (a, b) = (format_ident!("a{}", n), format_ident!("b{}", n));
+            quote!(.unify(#a, #b)?)
+        });
+        quote!((#left, #right) => Some(state #(#unifies)*),)
+    });
+
+    Ok(quote! {
+        impl #impl_generics canrun::UnifyIn<'a, D> for #name #ty_generics
+        where
+            D: canrun::DomainType<'a, Self> #(+ canrun::DomainType<'a, #inner>)*,
+            #(#inner: canrun::UnifyIn<'a, D>,)*
+            #predicates
+        {
+            fn unify_resolved(
+                state: canrun::State<'a, D>,
+                a: std::rc::Rc<Self>,
+                b: std::rc::Rc<Self>,
+            ) -> Option<canrun::State<'a, D>> {
+                #[allow(unreachable_patterns)]
+                match (&*a, &*b) {
+                    #(#arms)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn derive_reify_in(input: DeriveInput) -> syn::Result<TokenStream> {
+    let mut reified: syn::Path = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("reified"))
+        .ok_or_else(|| {
+            syn::Error::new(
+                input.ident.span(),
+                "deriving ReifyIn requires a `#[reified(Type)]` attribute",
+            )
+        })?
+        .parse_args()?;
+    // Generic arguments need a turbofish to be used in expression position.
+    for segment in reified.segments.iter_mut() {
+        if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+            args.colon2_token = Some(Default::default());
+        }
+    }
+    let reified_path = quote!(#reified);
+
+    let shapes = shapes(&input)?;
+    let inner = inner_types(&input, &shapes)?;
+    // Bare type parameters are required to reify into themselves (as the
+    // primitive types do) so that the `#[reified(...)]` type can name them.
+    let reify_bounds = inner
+        .iter()
+        .filter(|ty| is_generic(&input.generics, ty))
+        .map(|ty| {
+            let is_param = input
+                .generics
+                .type_params()
+                .any(|p| p.ident == quote!(#ty).to_string());
+            if is_param {
+                quote!(#ty: canrun::ReifyIn<'a, D, Reified = #ty>)
+            } else {
+                quote!(#ty: canrun::ReifyIn<'a, D>)
+            }
+        });
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, syn::parse_quote!('a));
+    generics.params.push(syn::parse_quote!(D));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|w| &w.predicates);
+
+    let arms = shapes.iter().map(|shape| {
+        let from = pattern(shape.path(&quote!(Self)), &shape.fields, "f");
+        let to_path = shape.path(&reified_path);
+        let values = (0..shape.fields.len()).map(|n| {
+            let f = format_ident!("f{}", n);
+            // `.into()` lets recursive types reify into a `Box<_>` field.
+            quote!(canrun::ReifyIn::reify_in(#f, state)?.into())
+        });
+        let to = match &shape.fields {
+            Fields::Named(named) => {
+                let idents = named.named.iter().map(|f| &f.ident);
+                quote!(#to_path { #(#idents: #values),* })
+            }
+            Fields::Unnamed(_) => quote!(#to_path(#(#values),*)),
+            Fields::Unit => quote!(#to_path),
+        };
+        quote!(#from => Some(#to),)
+    });
+
+    Ok(quote! {
+        impl #impl_generics canrun::ReifyIn<'a, D> for #name #ty_generics
+        where
+            D: canrun::DomainType<'a, Self> #(+ canrun::DomainType<'a, #inner>)* + 'a,
+            #(#reify_bounds,)*
+            #predicates
+        {
+            type Reified = #reified;
+            fn reify_in(&self, state: &canrun::ResolvedState<D>) -> Option<Self::Reified> {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
@@ -1,14 +1,19 @@
 extern crate proc_macro;
+mod defrel;
+mod derive;
+
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, Result, Token};
+use syn::{parse_macro_input, DeriveInput, Result, Token};
 
 struct DomainDef {
     canrun_internal: bool,
     domain_visibility: syn::Visibility,
     domain_name: syn::Ident,
+    domain_generics: syn::Generics,
+    domain_base: Option<syn::Type>,
     domain_types: Vec<syn::Type>,
 }
 
@@ -18,6 +23,26 @@ impl Parse for DomainDef {
 
         let domain_name: syn::Ident = input.parse()?;
 
+        let mut domain_generics: syn::Generics = input.parse()?;
+
+        let domain_base = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        domain_generics.where_clause = input.parse()?;
+        if let Some(lifetime) = domain_generics
+            .lifetimes()
+            .find(|l| l.lifetime.ident == "a")
+        {
+            return Err(syn::Error::new(
+                lifetime.lifetime.apostrophe,
+                "the 'a lifetime is reserved for the generated domain impls",
+            ));
+        }
+
         let content;
         syn::braced!(content in input);
 
@@ -29,6 +54,8 @@ impl Parse for DomainDef {
             canrun_internal: false,
             domain_visibility,
             domain_name,
+            domain_generics,
+            domain_base,
             domain_types,
         })
     }
@@ -40,6 +67,8 @@ impl quote::ToTokens for DomainDef {
             canrun_internal,
             domain_visibility,
             domain_name,
+            domain_generics,
+            domain_base,
             domain_types,
         } = self;
 
@@ -55,18 +84,165 @@ impl quote::ToTokens for DomainDef {
 
         let value_name = format_ident!("{}Value", domain_name);
 
+        // Every type stored in the domain must be `Debug`, which concrete types
+        // are checked for at the point of use. Generic types need explicit
+        // bounds.
+        let generic_types: Vec<_> = domain_types
+            .iter()
+            .filter(|ty| derive::is_generic(domain_generics, ty))
+            .collect();
+        let mut generics = domain_generics.clone();
+        {
+            let where_clause = generics.make_where_clause();
+            for ty in &generic_types {
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#ty: std::fmt::Debug));
+            }
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let predicates = where_clause.map(|w| &w.predicates);
+
+        // The impls are parameterized over the domain lifetime, which every
+        // generic parameter needs to outlive.
+        let outlives = generics.params.iter().filter_map(|param| match param {
+            syn::GenericParam::Type(t) => {
+                let ident = &t.ident;
+                Some(quote!(#ident: 'a))
+            }
+            syn::GenericParam::Lifetime(l) => {
+                let lifetime = &l.lifetime;
+                Some(quote!(#lifetime: 'a))
+            }
+            syn::GenericParam::Const(_) => None,
+        });
+        let domain_bounds = quote!(#(#outlives,)* #predicates);
+        let mut lifetime_generics = generics.clone();
+        lifetime_generics.params.insert(0, syn::parse_quote!('a));
+        let (impl_lifetime_generics, lifetime_ty_generics, _) = lifetime_generics.split_for_impl();
+
+        // Requiring `T: UnifyIn<'a, Self>` on the impls of a generic domain
+        // sends the trait solver into a loop, since `UnifyIn` itself requires
+        // `Self: DomainType<'a, T>`. Instead, each value carries a function to
+        // unify it, created in `into_domain_val` where that bound is available.
+        // Extended domains need the same lifetime for their base values.
+        let is_generic = !domain_generics.params.is_empty() || domain_base.is_some();
+        let domain_ty = quote!(#domain_name #ty_generics);
+        let (value_ty, value_def, value_variants, unify_arms, into_values, clone_arms) =
+            if is_generic {
+                (
+                    quote!(#value_name #lifetime_ty_generics),
+                    quote!(#value_name #lifetime_generics),
+                    quote!(#(#variants(
+                        #canrun_mod::value::Val<#domain_types>,
+                        #canrun_mod::domains::UnifyFn<'a, #domain_ty, #domain_types>,
+                    ),)*),
+                    quote!(#(
+                        (#value_name::#variants(a, unify), #value_name::#variants(b, _)) => {
+                            unify(state, a, b)
+                        }
+                    ,)*),
+                    variants
+                        .iter()
+                        .map(|v| quote!(#value_name::#v(val, |state, a, b| state.unify(&a, &b))))
+                        .collect::<Vec<_>>(),
+                    quote!(#(#value_name::#variants(val, unify) => #value_name::#variants(val.clone(), *unify),)*),
+                )
+            } else {
+                (
+                    quote!(#value_name),
+                    quote!(#value_name),
+                    quote!(#(#variants(#canrun_mod::value::Val<#domain_types>),)*),
+                    quote!(#(
+                        (#value_name::#variants(a), #value_name::#variants(b)) => {
+                            use #canrun_mod::value::IntoVal;
+                            state.unify::<#domain_types>(&a.into_val(), &b.into_val())
+                        }
+                    ,)*),
+                    variants
+                        .iter()
+                        .map(|v| quote!(#value_name::#v(val)))
+                        .collect::<Vec<_>>(),
+                    quote!(#(#value_name::#variants(val) => #value_name::#variants(val.clone()),)*),
+                )
+            };
+
+        // Values of the base domain's types are stored in the base domain and
+        // forwarded to with a blanket impl. Their concrete types are unknown
+        // here, so their domain values are type erased.
+        let (
+            base_field,
+            base_new,
+            base_clone,
+            base_variant,
+            base_unify_arm,
+            base_clone_arm,
+            base_impl,
+        ) = match domain_base {
+            Some(base) => {
+                let mut base_generics = lifetime_generics.clone();
+                base_generics.params.push(syn::parse_quote!(BaseType));
+                let (base_impl_generics, _, _) = base_generics.split_for_impl();
+                (
+                    quote!(base: #base,),
+                    quote!(base: <#base as #canrun_mod::domains::Domain<'a>>::new(),),
+                    quote!(base: self.base.clone(),),
+                    quote!(Base(#canrun_mod::domains::AnyValue<'a, #domain_ty>),),
+                    quote!((#value_name::Base(a), #value_name::Base(b)) => a.unify(state, &b),),
+                    quote!(#value_name::Base(val) => #value_name::Base(val.clone()),),
+                    quote! {
+                        impl #base_impl_generics #canrun_mod::domains::DomainType<'a, BaseType> for #domain_ty
+                        where
+                            BaseType: std::fmt::Debug + 'static,
+                            #base: #canrun_mod::domains::DomainType<'a, BaseType>,
+                            #domain_bounds
+                        {
+                            fn values_as_ref(
+                                &self,
+                            ) -> Option<&#canrun_mod::domains::DomainValues<BaseType>> {
+                                <#base as #canrun_mod::domains::DomainType<'a, BaseType>>::values_as_ref(&self.base)
+                            }
+                            fn values_as_mut(
+                                &mut self,
+                            ) -> &mut #canrun_mod::domains::DomainValues<BaseType> {
+                                <#base as #canrun_mod::domains::DomainType<'a, BaseType>>::values_as_mut(&mut self.base)
+                            }
+                            fn into_domain_val(val: #canrun_mod::value::Val<BaseType>) -> #value_ty
+                            where
+                                BaseType: #canrun_mod::UnifyIn<'a, Self>,
+                            {
+                                #value_name::Base(#canrun_mod::domains::AnyValue::new(val))
+                            }
+                        }
+                    },
+                )
+            }
+            None => Default::default(),
+        };
+
+        let value_impl_generics = if is_generic {
+            quote!(#impl_lifetime_generics)
+        } else {
+            quote!(#impl_generics)
+        };
+
         let result = quote! {
             #[doc="A custom Domain generated by the domain! macro."]
             #[doc="TODO: Figure out how to interpolate something useful here"]
             #[derive(std::fmt::Debug)]
-            #domain_visibility struct #domain_name {
-                #(#fields: #canrun_mod::domains::DomainValues<#domain_types>),*
+            #domain_visibility struct #domain_name #generics #where_clause {
+                #base_field
+                #(#fields: #canrun_mod::domains::DomainValues<#domain_types>,)*
             }
 
-            impl<'a> #canrun_mod::domains::Domain<'a> for #domain_name {
-                type Value = #value_name;
+            impl #impl_lifetime_generics #canrun_mod::domains::Domain<'a> for #domain_ty
+            where
+                #domain_bounds
+            {
+                type Value = #value_ty;
                 fn new() -> Self {
                     #domain_name {
+                        #base_new
                         #(#fields: #canrun_mod::domains::DomainValues::new(),)*
                     }
                 }
@@ -74,55 +250,62 @@ impl quote::ToTokens for DomainDef {
                     state: #canrun_mod::state::State<'a, Self>,
                     a: Self::Value,
                     b: Self::Value,
-                ) -> Option<#canrun_mod::state::State<Self>> {
-                    use #canrun_mod::value::{Val, IntoVal};
+                ) -> Option<#canrun_mod::state::State<'a, Self>> {
                     match (a, b) {
-                        #(
-                            (#value_name::#variants(a), #value_name::#variants(b)) => {
-                                state.unify::<#domain_types>(&a.into_val(), &b.into_val())
-                            }
-                        ,)*
+                        #unify_arms
+                        #base_unify_arm
                         _ => None, // This should only happen if a DomainVal constructor allows two values with different types.
                     }
                 }
             }
 
             #(
-                impl<'a> #canrun_mod::domains::DomainType<'a, #domain_types> for #domain_name {
+                impl #impl_lifetime_generics #canrun_mod::domains::DomainType<'a, #domain_types> for #domain_ty
+                where
+                    #domain_bounds
+                {
                     fn values_as_ref(
                         &self,
-                    ) -> &#canrun_mod::domains::DomainValues<#domain_types> {
-                        &self.#fields
+                    ) -> Option<&#canrun_mod::domains::DomainValues<#domain_types>> {
+                        Some(&self.#fields)
                     }
                     fn values_as_mut(
                         &mut self,
                     ) -> &mut #canrun_mod::domains::DomainValues<#domain_types> {
                         &mut self.#fields
                     }
-                    fn into_domain_val(val: #canrun_mod::value::Val<#domain_types>) -> #value_name {
-                        #value_name::#variants(val)
+                    fn into_domain_val(val: #canrun_mod::value::Val<#domain_types>) -> #value_ty
+                    where
+                        #domain_types: #canrun_mod::UnifyIn<'a, Self>,
+                    {
+                        #into_values
                     }
                 }
             )*
 
-            impl<'a> Clone for #domain_name {
+            #base_impl
+
+            impl #impl_generics Clone for #domain_ty #where_clause {
                 fn clone(&self) -> Self {
                     #domain_name {
-                        #(#fields: self.#fields.clone()),*
+                        #base_clone
+                        #(#fields: self.#fields.clone(),)*
                     }
                 }
             }
 
             #[doc(hidden)]
             #[derive(std::fmt::Debug)]
-            #domain_visibility enum #value_name {
-                #(#variants(#canrun_mod::value::Val<#domain_types>)),*
+            #domain_visibility enum #value_def #where_clause {
+                #value_variants
+                #base_variant
             }
 
-            impl Clone for #value_name {
+            impl #value_impl_generics Clone for #value_ty #where_clause {
                 fn clone(&self) -> Self {
                     match self {
-                        #(#value_name::#variants(val) => #value_name::#variants(val.clone())),*
+                        #clone_arms
+                        #base_clone_arm
                     }
                 }
             }
@@ -150,3 +333,51 @@ pub fn canrun_internal_domain(item: TokenStream) -> TokenStream {
     def.canrun_internal = true;
     quote!(#def).into()
 }
+
+/// Derive [`UnifyIn`](../canrun/unify/trait.UnifyIn.html) for structs and
+/// enums whose fields are all `Val<T>`s.
+///
+/// Structs unify field by field. Enums only unify when both values are the same
+/// variant, in which case their fields are unified.
+///
+/// See the [Canrun docs](../canrun/unify/trait.UnifyIn.html) for details.
+#[proc_macro_derive(UnifyIn)]
+pub fn derive_unify_in(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    derive::derive_unify_in(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derive [`ReifyIn`](../canrun/value/trait.ReifyIn.html) for structs and
+/// enums whose fields are all `Val<T>`s.
+///
+/// The `#[reified(Type)]` attribute names a plain type with the same shape
+/// (fields or variants) that each field is reified into. Generic type
+/// parameters must reify into themselves, as the primitive types do.
+///
+/// See the [Canrun docs](../canrun/unify/trait.UnifyIn.html) for details.
+#[proc_macro_derive(ReifyIn, attributes(reified))]
+pub fn derive_reify_in(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    derive::derive_reify_in(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Declare named relation functions.
+///
+/// Each parameter is written with the type of value it relates and accepts
+/// anything that implements [`IntoVal`](../canrun/value/trait.IntoVal.html).
+/// The body is wrapped in a [lazy](../canrun/goal/fn.lazy.html) goal, so
+/// relations can safely call themselves recursively. The relation's name is
+/// shown in debug output and logged at the `trace` level when it runs.
+///
+/// See the [Canrun docs](../canrun/macro.defrel.html) for details.
+#[proc_macro]
+pub fn defrel(item: TokenStream) -> TokenStream {
+    let relations = parse_macro_input!(item as defrel::Relations);
+    defrel::defrel(relations)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
@@ -1,11 +1,18 @@
 //! Example domains for Canrun collections
+use crate::lbtreemap::LBTreeMap;
+use crate::llist::LList;
 use crate::lmap::LMap;
+use crate::lset::LSet;
 use crate::lvec::LVec;
 
 canrun::domain! {
     pub Collections {
         i32,
+        usize,
+        LBTreeMap<i32, i32>,
+        LList<i32>,
         LMap<i32, i32>,
+        LSet<i32>,
         LVec<i32>
     }
 }
@@ -0,0 +1,247 @@
+//! A [`BTreeMap`](std::collections::BTreeMap)-like data structure with
+//! [`LVar`](canrun::value::LVar) keys and values.
+//!
+//! This works like an [`LMap`](crate::lmap::LMap), but entries are kept in key
+//! order. Forks happen in a predictable order and maps reify into a
+//! [`BTreeMap`], so the answers to a query come out the same way every time.
+use crate::matching::unify_unmatched;
+use canrun::state::constraints::VarWatch;
+use canrun::{DomainType, IntoVal, ReifyIn, ResolvedState, State, UnifyIn, Val};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+mod ordered;
+
+pub use ordered::{first_key, range};
+
+/// A [`BTreeMap`](std::collections::BTreeMap)-like data structure with
+/// [`LVar`](canrun::value::LVar) keys and values.
+///
+/// Resolved keys are ordered by value, followed by any unbound keys.
+#[derive(Debug, Clone)]
+pub struct LBTreeMap<K: Ord + Debug, V: Debug> {
+    map: BTreeMap<Val<K>, Val<V>>,
+}
+
+impl<K: Ord + Debug, V: Debug> LBTreeMap<K, V> {
+    /// Create a new [`LBTreeMap`] value.
+    ///
+    /// You may also be interested in the [`lbtreemap!`] macro.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_collections::lbtreemap::LBTreeMap;
+    ///
+    /// let map: LBTreeMap<i32, i32> = LBTreeMap::new();
+    /// ```
+    pub fn new() -> Self {
+        LBTreeMap {
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// Add a key/value pair to an existing [`LBTreeMap`].
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_collections::lbtreemap::LBTreeMap;
+    ///
+    /// let mut map: LBTreeMap<i32, i32> = LBTreeMap::new();
+    /// map.insert(1, 2);
+    /// ```
+    pub fn insert<Ki, Vi>(&mut self, key: Ki, value: Vi)
+    where
+        Ki: IntoVal<K>,
+        Vi: IntoVal<V>,
+    {
+        self.map.insert(key.into_val(), value.into_val());
+    }
+
+    fn resolve_in<'a, D>(&self, mut state: State<'a, D>) -> Option<(State<'a, D>, Rc<Self>)>
+    where
+        V: UnifyIn<'a, D>,
+        K: UnifyIn<'a, D>,
+        D: DomainType<'a, K> + DomainType<'a, V>,
+    {
+        let mut resolved: BTreeMap<Val<K>, Val<V>> = BTreeMap::new();
+        for (key, value) in self.map.iter() {
+            let resolved_key = state.resolve_val(key).clone();
+            let resolved_value = state.resolve_val(value).clone();
+            if let Some(existing_value) = resolved.insert(resolved_key, resolved_value) {
+                // Keys that turned out to be the same merge if their values unify.
+                state = state.unify(value, &existing_value)?;
+            }
+        }
+        Some((state, Rc::new(LBTreeMap { map: resolved })))
+    }
+
+    /// Clone the key/value pairs in key order.
+    fn entries(&self) -> Vec<(Val<K>, Val<V>)> {
+        self.map
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    /// Watch the first key that is still unbound, if there is one.
+    fn watch_keys<'a, D>(&self, state: &State<'a, D>) -> Result<(), VarWatch>
+    where
+        D: DomainType<'a, K>,
+    {
+        for key in self.map.keys() {
+            if let Val::Var(var) = state.resolve_val(key) {
+                return Err(VarWatch::one(*var));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K: Ord + Debug, V: Debug> Default for LBTreeMap<K, V> {
+    fn default() -> Self {
+        LBTreeMap::new()
+    }
+}
+
+impl<'a, K, V, D> UnifyIn<'a, D> for LBTreeMap<K, V>
+where
+    K: UnifyIn<'a, D> + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, Self>,
+{
+    fn unify_resolved(state: State<'a, D>, a: Rc<Self>, b: Rc<Self>) -> Option<State<'a, D>> {
+        let (state, a) = a.resolve_in(state)?;
+        let (mut state, b) = b.resolve_in(state)?;
+
+        let mut a_unmatched = Vec::new();
+        for (a_key, a_value) in a.map.iter() {
+            match b.map.get(a_key) {
+                Some(b_value) => state = state.unify(a_value, b_value)?,
+                None => a_unmatched.push((a_key.clone(), a_value.clone())),
+            }
+        }
+        let b_unmatched: Vec<_> = b
+            .map
+            .iter()
+            .filter(|(b_key, _)| !a.map.contains_key(b_key))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        unify_unmatched(
+            state,
+            (a_unmatched, a.entries()),
+            (b_unmatched, b.entries()),
+        )
+    }
+}
+
+impl<'a, D, Kv: Ord + Debug, Kr, Vv: Debug, Vr> ReifyIn<'a, D> for LBTreeMap<Kv, Vv>
+where
+    D: DomainType<'a, Kv> + DomainType<'a, Vv> + 'a,
+    Kv: ReifyIn<'a, D, Reified = Kr>,
+    Kr: Ord,
+    Vv: ReifyIn<'a, D, Reified = Vr>,
+{
+    type Reified = BTreeMap<Kr, Vr>;
+    fn reify_in(&self, state: &ResolvedState<D>) -> Option<Self::Reified> {
+        self.map
+            .iter()
+            .map(|(k, v)| Some((state.reify(k)?, state.reify(v)?)))
+            .collect()
+    }
+}
+
+/// Create an [`LBTreeMap`](crate::lbtreemap::LBTreeMap) with automatic
+/// key/value [`IntoVal` wrapping](canrun::value::IntoVal).
+///
+/// The primary benefit is that it allows freely mixing resolved values and
+/// [`LVar`s](canrun::value::LVar).
+///
+/// # Example:
+/// ```
+/// use canrun::var;
+/// use canrun_collections::lbtreemap::{lbtreemap, LBTreeMap};
+///
+/// let x = var();
+/// let map: LBTreeMap<i32, i32> = lbtreemap!{x => 1, 2 => 3};
+/// ```
+#[macro_export]
+macro_rules! lbtreemap {
+    ($($key:expr => $value:expr),* $(,)?) => {
+        {
+            let mut map = $crate::lbtreemap::LBTreeMap::new();
+            $(map.insert($key, $value);)*
+            map
+        }
+    };
+}
+
+#[doc(inline)]
+pub use lbtreemap;
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use canrun::{unify, util, var, Goal};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn succeeds_with_identical() {
+        let m = var();
+        let goals: Vec<Goal<Collections>> = vec![
+            unify(m, lbtreemap! {1 => 2, 3 => 4}),
+            unify(m, lbtreemap! {3 => 4, 1 => 2}),
+        ];
+        let expected: BTreeMap<_, _> = vec![(1, 2), (3, 4)].into_iter().collect();
+        util::assert_permutations_resolve_to(goals, m, vec![expected]);
+    }
+
+    #[test]
+    fn fails_with_different() {
+        let m = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(m, lbtreemap! {1 => 2}), unify(m, lbtreemap! {1 => 3})];
+        util::assert_permutations_resolve_to(goals, m, vec![]);
+    }
+
+    #[test]
+    fn variable_keys_and_values() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(
+            lbtreemap! {1 => y, x => 4},
+            lbtreemap! {1 => 2, 3 => 4},
+        )];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(3, 2)]);
+    }
+
+    #[test]
+    fn answers_in_key_order() {
+        let (x, y) = (var(), var());
+        let goal: Goal<Collections> =
+            unify(lbtreemap! {x => 0, y => 0}, lbtreemap! {2 => 0, 1 => 0});
+        let results: Vec<_> = goal.query((x, y)).collect();
+        assert_eq!(results, vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn mergeable_keys() {
+        let (m, x) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            unify(m, lbtreemap! {x => 1, 1 => 1}),
+            unify(m, lbtreemap! {1 => 1}),
+        ];
+        let expected: BTreeMap<_, _> = vec![(1, 1)].into_iter().collect();
+        util::assert_permutations_resolve_to(goals, (m, x), vec![(expected, 1)]);
+    }
+
+    #[test]
+    fn merges_without_duplicates() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(
+            lbtreemap! {x => 0, y => 0, 1 => 0},
+            lbtreemap! {1 => 0, 2 => 0},
+        )];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(2, 2), (2, 1), (1, 2)]);
+    }
+}
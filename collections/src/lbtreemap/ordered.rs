@@ -0,0 +1,185 @@
+use crate::lbtreemap::LBTreeMap;
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{resolve_1, resolve_2, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+
+/// Create a [`Goal`](canrun::goal) that relates an [`LBTreeMap`] to its
+/// smallest key.
+///
+/// The goal waits for the map and every key in it to be resolved. An empty map
+/// has no first key, so the goal fails.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lbtreemap::{self, lbtreemap};
+/// use canrun_collections::example::Collections;
+///
+/// let k = var();
+/// let goal: Goal<Collections> = lbtreemap::first_key(lbtreemap! {3 => 4, 1 => 2}, k);
+/// let results: Vec<_> = goal.query(k).collect();
+/// assert_eq!(results, vec![1]);
+/// ```
+pub fn first_key<'a, K, V, MV, KV, D>(map: MV, key: KV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LBTreeMap<K, V>>,
+    KV: IntoVal<K>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LBTreeMap<K, V>>,
+{
+    Goal::constraint(FirstKey {
+        map: map.into_val(),
+        key: key.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that relates an [`LBTreeMap`] to the
+/// entries with keys from `start` (inclusive) to `end` (exclusive).
+///
+/// The goal waits for the map, both bounds and every key in the map to be
+/// resolved.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lbtreemap::{self, lbtreemap};
+/// use canrun_collections::example::Collections;
+/// use std::collections::BTreeMap;
+///
+/// let m = var();
+/// let goal: Goal<Collections> =
+///     lbtreemap::range(lbtreemap! {1 => 2, 3 => 4, 5 => 6}, 2, 5, m);
+/// let results: Vec<_> = goal.query(m).collect();
+/// assert_eq!(results, vec![vec![(3, 4)].into_iter().collect::<BTreeMap<_, _>>()]);
+/// ```
+pub fn range<'a, K, V, MV, SV, EV, RV, D>(map: MV, start: SV, end: EV, range: RV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LBTreeMap<K, V>>,
+    SV: IntoVal<K>,
+    EV: IntoVal<K>,
+    RV: IntoVal<LBTreeMap<K, V>>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LBTreeMap<K, V>>,
+{
+    Goal::constraint(Range {
+        map: map.into_val(),
+        start: start.into_val(),
+        end: end.into_val(),
+        range: range.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct FirstKey<K: Ord + Debug, V: Debug> {
+    map: Val<LBTreeMap<K, V>>,
+    key: Val<K>,
+}
+
+impl<'a, K, V, D> Constraint<'a, D> for FirstKey<K, V>
+where
+    K: UnifyIn<'a, D> + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LBTreeMap<K, V>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let map = resolve_1(&self.map, state)?;
+        map.watch_keys(state)?;
+        let key = self.key.clone();
+        Ok(Box::new(move |state| {
+            let (state, map) = map.resolve_in(state)?;
+            let first = map.map.keys().next()?;
+            state.unify(first, &key)
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct Range<K: Ord + Debug, V: Debug> {
+    map: Val<LBTreeMap<K, V>>,
+    start: Val<K>,
+    end: Val<K>,
+    range: Val<LBTreeMap<K, V>>,
+}
+
+impl<'a, K, V, D> Constraint<'a, D> for Range<K, V>
+where
+    K: UnifyIn<'a, D> + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LBTreeMap<K, V>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let map = resolve_1(&self.map, state)?;
+        let (start, end) = resolve_2(&self.start, &self.end, state)?;
+        map.watch_keys(state)?;
+        let range = self.range.clone();
+        Ok(Box::new(move |state| {
+            let (state, map) = map.resolve_in(state)?;
+            let (start, end) = (Val::Resolved(start), Val::Resolved(end));
+            let found = if start < end {
+                map.map
+                    .range(start..end)
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect()
+            } else {
+                Default::default()
+            };
+            state.unify(&LBTreeMap { map: found }.into_val(), &range)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lbtreemap;
+    use crate::lbtreemap::LBTreeMap;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn first_key_waits_for_keys() {
+        let (k, x) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lbtreemap::first_key(lbtreemap! {x => 1, 3 => 4}, k),
+            unify(x, 5),
+        ];
+        util::assert_permutations_resolve_to(goals, k, vec![3]);
+    }
+
+    #[test]
+    fn first_key_of_empty_map_fails() {
+        let k = var();
+        let goals: Vec<Goal<Collections>> = vec![lbtreemap::first_key(LBTreeMap::new(), k)];
+        util::assert_permutations_resolve_to(goals, k, vec![]);
+    }
+
+    #[test]
+    fn range_binds_values() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lbtreemap::range(
+            lbtreemap! {1 => 2, 3 => x},
+            2,
+            4,
+            lbtreemap! {3 => 5},
+        )];
+        util::assert_permutations_resolve_to(goals, x, vec![5]);
+    }
+
+    #[test]
+    fn empty_range() {
+        let m = var();
+        let goals: Vec<Goal<Collections>> = vec![
+            lbtreemap::range(lbtreemap! {1 => 2}, 3, 2, m),
+            lbtreemap::first_key(m, var()),
+        ];
+        util::assert_permutations_resolve_to(goals, m, vec![]);
+    }
+}
@@ -13,10 +13,20 @@
 extern crate canrun;
 
 pub mod example;
+pub mod lbtreemap;
+pub mod llist;
 pub mod lmap;
+pub mod lset;
 pub mod lvec;
+mod matching;
 
+#[doc(hidden)]
+pub use lbtreemap::LBTreeMap;
+#[doc(hidden)]
+pub use llist::LList;
 #[doc(hidden)]
 pub use lmap::LMap;
 #[doc(hidden)]
+pub use lset::LSet;
+#[doc(hidden)]
 pub use lvec::LVec;
@@ -0,0 +1,193 @@
+//! A cons-list with [`LVar`](canrun::value::LVar) values and tails.
+//!
+//! Unlike an [`LVec`](crate::lvec::LVec), the tail of an [`LList`] may be an
+//! unbound variable. This makes it possible to describe lists of unknown
+//! length, and to write relations that run "backwards" to generate lists.
+
+mod append;
+mod length;
+mod member;
+mod nth;
+mod reverse;
+
+pub use append::append;
+pub use length::length;
+pub use member::member;
+pub use nth::nth;
+pub use reverse::reverse;
+
+use canrun::{DomainType, IntoVal, ReifyIn, ResolvedState, State, UnifyIn, Val};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// A cons-list with [`LVar`](canrun::value::LVar) values and tails.
+///
+/// Lists are typically created with the [`llist!`] macro. An `LList<T>`
+/// [reifies](canrun::value::ReifyIn) into a `Vec<T>`, but only once the entire
+/// spine of the list is resolved.
+#[derive(Debug, Clone)]
+pub enum LList<T: Debug> {
+    /// The empty list.
+    Nil,
+    /// A value followed by the rest of the list.
+    Cons(Val<T>, Val<LList<T>>),
+}
+
+impl<T: Debug> LList<T> {
+    /// Create an empty [`LList`].
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_collections::llist::LList;
+    ///
+    /// let list: LList<i32> = LList::nil();
+    /// ```
+    pub fn nil() -> Self {
+        LList::Nil
+    }
+
+    /// Create an [`LList`] from a head value and a tail list.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun::var;
+    /// use canrun_collections::llist::LList;
+    ///
+    /// let list: LList<i32> = LList::cons(1, var());
+    /// ```
+    pub fn cons<H, TV>(head: H, tail: TV) -> Self
+    where
+        H: IntoVal<T>,
+        TV: IntoVal<LList<T>>,
+    {
+        LList::Cons(head.into_val(), tail.into_val())
+    }
+}
+
+/// Create an [`LList<T>`](llist::LList) with automatic value [`IntoVal`
+/// wrapping](canrun::value::IntoVal).
+///
+/// Items are separated with commas. An optional tail can be added after a
+/// semicolon, which is typically an [`LVar`](canrun::value::LVar) standing in
+/// for the rest of the list.
+///
+/// # Example:
+/// ```
+/// use canrun::var;
+/// use canrun_collections::{llist, LList};
+/// let x = var();
+/// let list: LList<i32> = llist![x, 1, 2];
+/// let open: LList<i32> = llist![1, 2; var()];
+/// ```
+#[macro_export]
+macro_rules! llist {
+    () => {
+        $crate::llist::LList::Nil
+    };
+    ($($item:expr),+ $(,)?) => {
+        $crate::llist!($($item),+; $crate::llist::LList::Nil)
+    };
+    ($head:expr $(, $item:expr)*; $tail:expr) => {
+        $crate::llist::LList::cons($head, $crate::llist!($($item),*; $tail))
+    };
+    (; $tail:expr) => {
+        $tail
+    };
+}
+
+#[doc(inline)]
+pub use llist;
+
+impl<'a, T, D> UnifyIn<'a, D> for LList<T>
+where
+    T: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    fn unify_resolved(
+        state: State<'a, D>,
+        a: Rc<LList<T>>,
+        b: Rc<LList<T>>,
+    ) -> Option<State<'a, D>> {
+        match (&*a, &*b) {
+            (LList::Nil, LList::Nil) => Some(state),
+            (LList::Cons(a_head, a_tail), LList::Cons(b_head, b_tail)) => {
+                state.unify(a_head, b_head)?.unify(a_tail, b_tail)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, D, T> ReifyIn<'a, D> for LList<T>
+where
+    T: ReifyIn<'a, D> + Debug,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>> + 'a,
+{
+    type Reified = Vec<T::Reified>;
+    fn reify_in(&self, state: &ResolvedState<D>) -> Option<Self::Reified> {
+        let mut items = Vec::new();
+        let mut list = self;
+        while let LList::Cons(head, tail) = list {
+            items.push(head.reify_in(state)?);
+            list = state.resolve_val(tail).resolved().ok()?;
+        }
+        Some(items)
+    }
+}
+
+impl<T, I, IV> From<I> for LList<T>
+where
+    T: Debug,
+    IV: IntoVal<T>,
+    I: IntoIterator<Item = IV>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    fn from(items: I) -> Self {
+        items
+            .into_iter()
+            .rev()
+            .fold(LList::Nil, |tail, head| LList::cons(head, tail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use canrun::{unify, util, var, Goal};
+
+    #[test]
+    fn succeeds() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, llist![1, 2]), unify(x, llist![1, 2])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, llist![1, 3]), unify(x, llist![1, 2])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn different_lengths_fail() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, llist![1]), unify(x, llist![1, 2])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn unbound_tail() {
+        let (x, tail) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, llist![1; tail]), unify(x, llist![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, tail, vec![vec![2, 3]]);
+    }
+
+    #[test]
+    fn does_not_reify_open_lists() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, llist![1; var()])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
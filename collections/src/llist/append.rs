@@ -0,0 +1,110 @@
+use crate::llist::LList;
+use canrun::goal::{all, either, unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{fresh, DomainType, UnifyIn};
+
+/// Create a [`Goal`](canrun::goal) that relates two lists to the result of
+/// appending them together.
+///
+/// Any of the lists may contain unbound variables, so this can be used to
+/// split a list into every possible prefix and suffix.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{llist, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = llist::append(llist![1, 2], llist![3], x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![1, 2, 3]]);
+/// ```
+///
+/// ```
+/// # use canrun::{Goal, var};
+/// # use canrun_collections::{llist, example::Collections};
+/// let (x, y) = (var(), var());
+/// let goal: Goal<Collections> = llist::append(x, y, llist![1, 2]);
+/// let results: Vec<_> = goal.query((x, y)).collect();
+/// assert_eq!(results, vec![
+///     (vec![], vec![1, 2]),
+///     (vec![1], vec![2]),
+///     (vec![1, 2], vec![]),
+/// ]);
+/// ```
+pub fn append<'a, T, AV, BV, CV, D>(a: AV, b: BV, ab: CV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    AV: IntoVal<LList<T>>,
+    BV: IntoVal<LList<T>>,
+    CV: IntoVal<LList<T>>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    append_vals(a.into_val(), b.into_val(), ab.into_val())
+}
+
+fn append_vals<'a, T, D>(a: Val<LList<T>>, b: Val<LList<T>>, ab: Val<LList<T>>) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    either(
+        all![
+            unify(a.clone(), LList::Nil),
+            unify::<LList<T>, _, _, D>(b.clone(), ab.clone())
+        ],
+        fresh!(|head: T, a_tail: LList<T>, ab_tail: LList<T>| all![
+            unify(a.clone(), LList::cons(head, a_tail)),
+            unify(ab.clone(), LList::cons(head, ab_tail)),
+            append_vals(a_tail.into_val(), b.clone(), ab_tail.into_val()),
+        ]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::llist;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn forwards() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::append(llist![1], llist![2, 3], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn missing_prefix() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::append(x, llist![3], llist![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn all_splits() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![llist::append(x, y, llist![1, 2])];
+        util::assert_permutations_resolve_to(
+            goals,
+            (x, y),
+            vec![
+                (vec![], vec![1, 2]),
+                (vec![1], vec![2]),
+                (vec![1, 2], vec![]),
+            ],
+        );
+    }
+
+    #[test]
+    fn with_conditions() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![llist::append(x, y, llist![1, 2, 3]), unify(y, llist![2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1]]);
+    }
+}
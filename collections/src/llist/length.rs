@@ -0,0 +1,112 @@
+use crate::llist::LList;
+use canrun::goal::{all, either, unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{assert_1, fresh, DomainType, UnifyIn};
+
+/// Create a [`Goal`](canrun::goal) that relates a list to its length.
+///
+/// If the length is known, lists of that length will be generated. If neither
+/// is known, lists of every length will be generated in order.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{llist, example::Collections};
+///
+/// let n = var();
+/// let goal: Goal<Collections> = llist::length(llist![1, 2, 3], n);
+/// let results: Vec<_> = goal.query(n).collect();
+/// assert_eq!(results, vec![3]);
+/// ```
+///
+/// ```
+/// # use canrun::{Goal, var, all, unify};
+/// # use canrun_collections::{llist, example::Collections};
+/// let (x, a, b) = (var(), var(), var());
+/// let goal: Goal<Collections> = all![
+///     llist::length(x, 2),
+///     unify(x, llist![a; llist![b]]),
+///     unify(a, 1),
+///     unify(b, 2),
+/// ];
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![1, 2]]);
+/// ```
+pub fn length<'a, T, LV, NV, D>(list: LV, len: NV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    LV: IntoVal<LList<T>>,
+    NV: IntoVal<usize>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>> + DomainType<'a, usize>,
+{
+    length_from(list.into_val(), len.into_val(), 0)
+}
+
+fn length_from<'a, T, D>(list: Val<LList<T>>, len: Val<usize>, counted: usize) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>> + DomainType<'a, usize>,
+{
+    either(
+        all![unify(list.clone(), LList::Nil), unify(len.clone(), counted)],
+        all![
+            // Stop generating longer lists once the length is known.
+            assert_1(len.clone(), move |len: &usize| *len > counted),
+            fresh!(|head: T, tail: LList<T>| all![
+                unify(list.clone(), LList::cons(head, tail)),
+                length_from(tail.into_val(), len.clone(), counted + 1),
+            ]),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::llist;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn of_known_list() {
+        let n = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::length(llist![1, 2], n)];
+        util::assert_permutations_resolve_to(goals, n, vec![2]);
+    }
+
+    #[test]
+    fn of_empty_list() {
+        let n = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::length(llist![], n)];
+        util::assert_permutations_resolve_to(goals, n, vec![0]);
+    }
+
+    #[test]
+    fn mismatched_length() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, llist![1, 2]), llist::length(x, 3)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn generates_list_of_length() {
+        let (x, tail) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            llist::length(x, 3),
+            unify(x, llist![1, 2; tail]),
+            unify(tail, llist![3; var()]),
+        ];
+        util::assert_permutations_resolve_to(goals, tail, vec![vec![3]]);
+    }
+
+    #[test]
+    fn enumerates_lengths() {
+        let (x, n) = (var::<llist::LList<i32>>(), var());
+        let goal: Goal<Collections> = llist::length(x, n);
+        let results: Vec<usize> = goal.query(n).take(3).collect();
+        assert_eq!(results, vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::llist::LList;
+use canrun::goal::{all, either, unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{fresh, DomainType, UnifyIn};
+
+/// Create a [`Goal`](canrun::goal) that attempts to unify a `Val<T>` with any
+/// of the items in an `LList<T>`.
+///
+/// If the list has an unbound tail, it will be extended to generate new lists
+/// containing the item.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{llist, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = llist::member(x, llist![1, 2, 3]);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![1, 2, 3]);
+/// ```
+///
+/// ```
+/// # use canrun::{Goal, var, all};
+/// # use canrun_collections::{llist, example::Collections};
+/// let x = var();
+/// let goal: Goal<Collections> = all![
+///     llist::member(1, x),
+///     llist::member(2, x),
+///     llist::length(x, 2),
+/// ];
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![1, 2], vec![2, 1]]);
+/// ```
+pub fn member<'a, T, IV, LV, D>(item: IV, list: LV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    IV: IntoVal<T>,
+    LV: IntoVal<LList<T>>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    member_vals(item.into_val(), list.into_val())
+}
+
+fn member_vals<'a, T, D>(item: Val<T>, list: Val<LList<T>>) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    fresh!(|head: T, tail: LList<T>| all![
+        unify(list.clone(), LList::cons(head, tail)),
+        either(
+            unify::<T, _, _, D>(item.clone(), head),
+            member_vals(item.clone(), tail.into_val()),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::llist;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn basic_member() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::member(x, llist![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn member_with_conditions() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, 2), llist::member(x, llist![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![2]);
+    }
+
+    #[test]
+    fn not_a_member() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, llist![1, 2, 3]), llist::member(4, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn generates_lists() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![
+            llist::member(1, x),
+            llist::member(2, x),
+            llist::length(x, 2),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 2], vec![2, 1]]);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::llist::LList;
+use canrun::goal::{all, either, unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{assert_1, fresh, DomainType, UnifyIn};
+
+/// Create a [`Goal`](canrun::goal) that relates a list to the item at a
+/// (zero based) index.
+///
+/// If the index is not known, every index with a matching item is found.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{llist, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = llist::nth(llist![1, 2, 3], 1, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![2]);
+/// ```
+///
+/// ```
+/// # use canrun::{Goal, var};
+/// # use canrun_collections::{llist, example::Collections};
+/// let i = var();
+/// let goal: Goal<Collections> = llist::nth(llist![1, 2, 1], i, 1);
+/// let results: Vec<_> = goal.query(i).collect();
+/// assert_eq!(results, vec![0, 2]);
+/// ```
+pub fn nth<'a, T, LV, NV, IV, D>(list: LV, index: NV, item: IV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    LV: IntoVal<LList<T>>,
+    NV: IntoVal<usize>,
+    IV: IntoVal<T>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>> + DomainType<'a, usize>,
+{
+    nth_from(list.into_val(), index.into_val(), item.into_val(), 0)
+}
+
+fn nth_from<'a, T, D>(
+    list: Val<LList<T>>,
+    index: Val<usize>,
+    item: Val<T>,
+    skipped: usize,
+) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>> + DomainType<'a, usize>,
+{
+    fresh!(|head: T, tail: LList<T>| all![
+        unify(list.clone(), LList::cons(head, tail)),
+        either(
+            all![
+                unify(index.clone(), skipped),
+                unify::<T, _, _, D>(item.clone(), head)
+            ],
+            all![
+                // Don't walk past a known index into an open ended list.
+                assert_1(index.clone(), move |index: &usize| *index > skipped),
+                nth_from(tail.into_val(), index.clone(), item.clone(), skipped + 1),
+            ],
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::llist;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn known_index() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::nth(llist![1, 2, 3], 2, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![3]);
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::nth(llist![1, 2, 3], 3, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn finds_indexes() {
+        let i = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::nth(llist![3, 2, 3], i, 3)];
+        util::assert_permutations_resolve_to(goals, i, vec![0, 2]);
+    }
+
+    #[test]
+    fn places_item_in_open_list() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![
+            llist::nth(x, 1, 5),
+            llist::length(x, 2),
+            unify(x, llist![4; var()]),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![4, 5]]);
+    }
+}
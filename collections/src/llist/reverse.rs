@@ -0,0 +1,118 @@
+use crate::llist::LList;
+use canrun::goal::{all, either, unify, Goal};
+use canrun::value::{IntoVal, Val};
+use canrun::{fresh, DomainType, UnifyIn};
+
+/// Create a [`Goal`](canrun::goal) that relates a list to the same list in
+/// reverse order.
+///
+/// This works in either direction, as long as the length of one of the lists
+/// is known.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{llist, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = llist::reverse(x, llist![1, 2, 3]);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![3, 2, 1]]);
+/// ```
+pub fn reverse<'a, T, AV, BV, D>(a: AV, b: BV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    AV: IntoVal<LList<T>>,
+    BV: IntoVal<LList<T>>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    let (a, b) = (a.into_val(), b.into_val());
+    // Fixing the shape of both lists first keeps the accumulator from growing
+    // forever when only the reversed list is known.
+    all![
+        same_length(a.clone(), b.clone()),
+        reverse_onto(a, LList::Nil.into_val(), b),
+    ]
+}
+
+fn same_length<'a, T, D>(a: Val<LList<T>>, b: Val<LList<T>>) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    either(
+        all![unify(a.clone(), LList::Nil), unify(b.clone(), LList::Nil)],
+        fresh!(
+            |a_head: T, a_tail: LList<T>, b_head: T, b_tail: LList<T>| all![
+                unify(a.clone(), LList::cons(a_head, a_tail)),
+                unify(b.clone(), LList::cons(b_head, b_tail)),
+                same_length(a_tail.into_val(), b_tail.into_val()),
+            ]
+        ),
+    )
+}
+
+fn reverse_onto<'a, T, D>(
+    list: Val<LList<T>>,
+    reversed: Val<LList<T>>,
+    out: Val<LList<T>>,
+) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + 'a,
+    LList<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LList<T>>,
+{
+    either(
+        all![
+            unify(list.clone(), LList::Nil),
+            unify::<LList<T>, _, _, D>(reversed.clone(), out.clone())
+        ],
+        fresh!(|head: T, tail: LList<T>| all![
+            unify(list.clone(), LList::cons(head, tail)),
+            reverse_onto(
+                tail.into_val(),
+                LList::cons(head, reversed.clone()).into_val(),
+                out.clone(),
+            ),
+        ]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::llist;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn forwards() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::reverse(llist![1, 2, 3], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![3, 2, 1]]);
+    }
+
+    #[test]
+    fn backwards() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![llist::reverse(x, llist![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![3, 2, 1]]);
+    }
+
+    #[test]
+    fn palindromes() {
+        let (x, a) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, llist![1, a, 1]), llist::reverse(x, x), unify(a, 2)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 2, 1]]);
+    }
+
+    #[test]
+    fn not_reversed() {
+        let goals: Vec<Goal<Collections>> = vec![llist::reverse(llist![1, 2], llist![1, 2])];
+        util::assert_permutations_resolve_to(goals, var::<i32>(), vec![]);
+    }
+}
@@ -1,7 +1,8 @@
 //! A [`HashMap`](std::collections::HashMap)-like data structure with
 //! [`LVar`](canrun::value::LVar) keys and values.
-use canrun::state::{Fork, StateIter};
-use canrun::{DomainType, IntoVal, ReifyIn, ResolvedState, State, UnifyIn, Val};
+use crate::matching::{unify_unmatched, Cover, Leftover, Matching};
+use canrun::state::constraints::VarWatch;
+use canrun::{var, DomainType, IntoVal, ReifyIn, ResolvedState, State, UnifyIn, Val};
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
@@ -9,14 +10,25 @@ use std::hash::Hash;
 use std::rc::Rc;
 
 mod compare;
+mod entries;
+mod get;
+mod update;
 
 pub use compare::{is_subset, is_superset};
+pub use entries::{keys, values};
+pub use get::{contains_key, get};
+pub use update::{insert, remove};
 
 /// A [`HashMap`](std::collections::HashMap)-like data structure with
 /// [`LVar`](canrun::value::LVar) keys and values.
+///
+/// A map may be left open with a "rest" value standing in for any other
+/// entries, like a record with a row variable. Unifying `{1 => x | r}` with
+/// `{1 => 2, 3 => 4}` binds `x` to `2` and `r` to `{3 => 4}`.
 #[derive(Debug, Clone)]
 pub struct LMap<K: Eq + Hash + Debug, V: Debug> {
     map: HashMap<Val<K>, Val<V>>,
+    rest: Option<Val<LMap<K, V>>>,
 }
 
 impl<K: Eq + Hash + Debug, V: Debug> LMap<K, V> {
@@ -33,6 +45,7 @@ impl<K: Eq + Hash + Debug, V: Debug> LMap<K, V> {
     pub fn new() -> Self {
         LMap {
             map: HashMap::new(),
+            rest: None,
         }
     }
 
@@ -53,25 +66,107 @@ impl<K: Eq + Hash + Debug, V: Debug> LMap<K, V> {
         self.map.insert(key.into_val(), value.into_val());
     }
 
+    /// Leave the [`LMap`] open, with the remaining entries unified with
+    /// `rest`.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun::{var, unify, Goal};
+    /// use canrun_collections::lmap::{lmap, LMap};
+    /// use canrun_collections::example::Collections;
+    ///
+    /// let (x, rest) = (var(), var());
+    /// let mut open: LMap<i32, i32> = lmap! {1 => x};
+    /// open.set_rest(rest);
+    ///
+    /// let goal: Goal<Collections> = unify(open, lmap! {1 => 2, 3 => 4});
+    /// let results: Vec<_> = goal.query((x, rest)).collect();
+    /// assert_eq!(results, vec![(2, vec![(3, 4)].into_iter().collect())]);
+    /// ```
+    pub fn set_rest<R>(&mut self, rest: R)
+    where
+        R: IntoVal<LMap<K, V>>,
+    {
+        self.rest = Some(rest.into_val());
+    }
+
+    /// Resolve keys and values, merging any keys that turned out to be the
+    /// same and pulling in the entries of a resolved rest.
     fn resolve_in<'a, D>(&self, state: State<'a, D>) -> Option<(State<'a, D>, Rc<Self>)>
     where
         V: UnifyIn<'a, D>,
         K: UnifyIn<'a, D>,
-        D: DomainType<'a, K> + DomainType<'a, V>,
+        D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, Self>,
     {
         let mut state = state;
         let mut resolved: HashMap<Val<K>, Val<V>> = HashMap::new();
-        for (key, value) in self.map.iter() {
-            let resolved_key = state.resolve_val(&key).clone();
-            let resolved_value = state.resolve_val(&value).clone();
-            let existing = resolved.insert(resolved_key, resolved_value);
-            if let Some(existing_value) = existing {
-                // A variable key could end up being the same as an already
-                // resolved one. They're allowed to merge IF the values unify.
-                state = state.unify(&value, &existing_value)?;
+        let mut next: Option<Rc<Self>> = None;
+        loop {
+            let current: &Self = next.as_deref().unwrap_or(self);
+            for (key, value) in current.map.iter() {
+                let resolved_key = state.resolve_val(&key).clone();
+                let resolved_value = state.resolve_val(&value).clone();
+                let existing = resolved.insert(resolved_key, resolved_value);
+                if let Some(existing_value) = existing {
+                    // A variable key could end up being the same as an already
+                    // resolved one. They're allowed to merge IF the values unify.
+                    state = state.unify(&value, &existing_value)?;
+                }
+            }
+            match current
+                .rest
+                .as_ref()
+                .map(|rest| state.resolve_val(rest).clone())
+            {
+                Some(Val::Resolved(rest)) => next = Some(rest),
+                rest => {
+                    return Some((
+                        state,
+                        Rc::new(LMap {
+                            map: resolved,
+                            rest,
+                        }),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Clone the key/value pairs, not including any rest.
+    fn entries(&self) -> Vec<(Val<K>, Val<V>)> {
+        self.map
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    /// Watch the rest of an open map until it is resolved all the way down.
+    fn watch_rest<'a, D>(&self, state: &State<'a, D>) -> Result<(), VarWatch>
+    where
+        D: DomainType<'a, Self>,
+    {
+        match self.rest.as_ref().map(|rest| state.resolve_val(rest)) {
+            Some(Val::Var(var)) => Err(VarWatch::one(*var)),
+            Some(Val::Resolved(rest)) => rest.watch_rest(state),
+            None => Ok(()),
+        }
+    }
+
+    /// Watch the first key or rest that is still unbound, if there is one.
+    fn watch_entries<'a, D>(&self, state: &State<'a, D>) -> Result<(), VarWatch>
+    where
+        D: DomainType<'a, K> + DomainType<'a, Self>,
+    {
+        for key in self.map.keys() {
+            if let Val::Var(var) = state.resolve_val(key) {
+                return Err(VarWatch::one(*var));
             }
         }
-        Some((state, Rc::new(LMap { map: resolved })))
+        match self.rest.as_ref().map(|rest| state.resolve_val(rest)) {
+            Some(Val::Var(var)) => Err(VarWatch::one(*var)),
+            Some(Val::Resolved(rest)) => rest.watch_entries(state),
+            None => Ok(()),
+        }
     }
 }
 
@@ -83,87 +178,163 @@ where
 {
     fn unify_resolved(state: State<'a, D>, a: Rc<Self>, b: Rc<Self>) -> Option<State<'a, D>> {
         let (state, a) = a.resolve_in(state)?;
-        let (state, b) = b.resolve_in(state)?;
-        let state = unify_entries(state, a.clone(), b.clone())?;
-        let state = unify_entries(state, b, a)?;
-        Some(state)
+        let (mut state, b) = b.resolve_in(state)?;
+        // An open map only needs to match some of the other map's entries,
+        // so make sure that if only one side is open, it's `a`.
+        let (a, b) = match (&a.rest, &b.rest) {
+            (None, Some(_)) => (b, a),
+            _ => (a, b),
+        };
+
+        // Keys that are already identical pair up without any searching.
+        let mut a_unmatched = Vec::new();
+        for (a_key, a_value) in a.map.iter() {
+            match b.map.get(a_key) {
+                Some(b_value) => state = state.unify(a_value, b_value)?,
+                None => a_unmatched.push((a_key.clone(), a_value.clone())),
+            }
+        }
+        let b_unmatched: Vec<_> = b
+            .map
+            .iter()
+            .filter(|(b_key, _)| !a.map.contains_key(b_key))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        match (&a.rest, &b.rest) {
+            (Some(a_rest), Some(b_rest)) => {
+                // Both maps are open, so each rest takes the other map's
+                // unmatched entries plus a shared remainder. Only keys that are
+                // already identical are paired up.
+                if let (Val::Var(a_var), Val::Var(b_var)) = (a_rest, b_rest) {
+                    if a_var == b_var {
+                        return if a_unmatched.is_empty() && b_unmatched.is_empty() {
+                            Some(state)
+                        } else {
+                            None
+                        };
+                    }
+                }
+                let remainder: Val<Self> = var().into_val();
+                let for_a = LMap {
+                    map: b_unmatched.into_iter().collect(),
+                    rest: Some(remainder.clone()),
+                };
+                let for_b = LMap {
+                    map: a_unmatched.into_iter().collect(),
+                    rest: Some(remainder),
+                };
+                state
+                    .unify(a_rest, &for_a.into_val())?
+                    .unify(b_rest, &for_b.into_val())
+            }
+            (Some(a_rest), None) if a_unmatched.is_empty() => {
+                let leftover = LMap {
+                    map: b_unmatched.into_iter().collect(),
+                    rest: None,
+                };
+                state.unify(a_rest, &leftover.into_val())
+            }
+            (Some(a_rest), None) if a_unmatched.len() <= b_unmatched.len() => {
+                // The entries of `b` left over after pairing go to the rest.
+                state.fork(Rc::new(Matching {
+                    a: a_unmatched,
+                    b: b_unmatched,
+                    leftover: Some(rest_takes(a_rest.clone())),
+                }))
+            }
+            (Some(a_rest), None) if a_unmatched.iter().any(|(key, _)| key.is_var()) => {
+                // There are too many entries to pair one-to-one, so some
+                // unbound keys have to merge. Whatever `a` doesn't cover goes
+                // to the rest.
+                state.fork(Rc::new(Cover {
+                    from: a.entries(),
+                    to: b.entries(),
+                    leftover: Some(rest_takes(a_rest.clone())),
+                }))
+            }
+            (Some(_), None) => None,
+            _ => unify_unmatched(
+                state,
+                (a_unmatched, a.entries()),
+                (b_unmatched, b.entries()),
+            ),
+        }
     }
 }
 
+/// Unify the rest of an open map with the entries left over after matching.
+fn rest_takes<'a, K, V, D>(rest: Val<LMap<K, V>>) -> Leftover<'a, D, (Val<K>, Val<V>)>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + fmt::Debug + 'a,
+    V: UnifyIn<'a, D> + fmt::Debug + 'a,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
+{
+    Rc::new(move |state: State<'a, D>, leftover| {
+        let leftover = LMap {
+            map: leftover.into_iter().collect(),
+            rest: None,
+        };
+        state.unify(&rest, &leftover.into_val())
+    })
+}
+
+/// Require every entry in `a` to unify with some entry in `b`.
+///
+/// The entries of any resolved rests are included. Unresolved rests are
+/// ignored, so callers should wait for them first.
 fn unify_entries<'a, K, V, D>(
-    mut state: State<'a, D>,
+    state: State<'a, D>,
     a: Rc<LMap<K, V>>,
     b: Rc<LMap<K, V>>,
 ) -> Option<State<'a, D>>
 where
     K: UnifyIn<'a, D> + Eq + Hash + fmt::Debug + 'a,
     V: UnifyIn<'a, D> + fmt::Debug + 'a,
-    D: DomainType<'a, K> + DomainType<'a, V>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
 {
+    let (state, a) = a.resolve_in(state)?;
+    let (mut state, b) = b.resolve_in(state)?;
+    // Keys that are already identical pair up without any forking.
+    let mut a_unmatched = Vec::new();
     for (a_key, a_value) in a.map.iter() {
-        // In the best case, all of the keys in `a` exist in both maps
-        if let Some(b_value) = b.map.get(a_key) {
-            // So we can unify directly and continue or bail
-            state = state.unify(a_value, b_value)?;
-        } else {
-            // Otherwise, we need to consider every possible match, which means
-            // forking. The bad news is that this could blow up to a lot of
-            // alternates if the map is large. The good news is that even if we
-            // queue up a fork, any other matching keys that fail to unify will
-            // abort the whole state.
-            state = state.fork(Rc::new(LMapFork {
-                a_key: a_key.clone(),
-                a_value: a_value.clone(),
-                b_map: b.clone(),
-            }))?;
+        match b.map.get(a_key) {
+            Some(b_value) => state = state.unify(a_value, b_value)?,
+            None => a_unmatched.push((a_key.clone(), a_value.clone())),
         }
     }
-    Some(state)
-}
-
-#[derive(Debug)]
-struct LMapFork<K: Eq + Hash + Debug, V: Debug> {
-    a_key: Val<K>,
-    a_value: Val<V>,
-    b_map: Rc<LMap<K, V>>,
-}
-
-impl<'a, K: Eq + Hash + Debug, V: Debug, D> Fork<'a, D> for LMapFork<K, V>
-where
-    K: UnifyIn<'a, D> + 'a,
-    V: UnifyIn<'a, D> + 'a,
-    D: DomainType<'a, K> + DomainType<'a, V>,
-{
-    fn fork(&self, state: State<'a, D>) -> StateIter<'a, D> {
-        let a_key = self.a_key.clone();
-        let a_value = self.a_value.clone();
-        let b_map = self.b_map.map.clone();
-        Box::new(b_map.into_iter().filter_map(move |(b_key, b_value)| {
-            state
-                .clone()
-                .unify(&a_key, &b_key)?
-                .unify(&a_value, &b_value)
+    if a_unmatched.is_empty() {
+        Some(state)
+    } else {
+        state.fork(Rc::new(Cover {
+            from: a_unmatched,
+            to: b.entries(),
+            leftover: None,
         }))
     }
 }
 
 impl<'a, D, Kv: Eq + Hash + Debug, Kr, Vv: Debug, Vr> ReifyIn<'a, D> for LMap<Kv, Vv>
 where
-    D: DomainType<'a, Kv> + DomainType<'a, Vv> + 'a,
+    D: DomainType<'a, Kv> + DomainType<'a, Vv> + DomainType<'a, LMap<Kv, Vv>> + 'a,
     Kv: ReifyIn<'a, D, Reified = Kr>,
     Kr: Eq + Hash,
     Vv: ReifyIn<'a, D, Reified = Vr>,
 {
     type Reified = HashMap<Kr, Vr>;
     fn reify_in(&self, state: &ResolvedState<D>) -> Option<Self::Reified> {
-        let LMap { map } = self;
-        let init = HashMap::with_capacity(map.len());
-        map.iter().try_fold(init, |mut map, (k, v)| {
-            let key = state.reify(k)?;
-            let value = state.reify(v)?;
-            map.insert(key, value);
-            Some(map)
-        })
+        let mut reified = HashMap::with_capacity(self.map.len());
+        let mut current = self;
+        loop {
+            for (k, v) in current.map.iter() {
+                reified.insert(state.reify(k)?, state.reify(v)?);
+            }
+            // An open map can only be reified once its rest is known.
+            match &current.rest {
+                Some(rest) => current = state.resolve_val(rest).resolved().ok()?,
+                None => return Some(reified),
+            }
+        }
     }
 }
 
@@ -181,15 +352,32 @@ where
 /// let x = var();
 /// let map: LMap<i32, i32> = lmap!{x => 1, 2 => 3};
 /// ```
+///
+/// An open map can be created by adding a rest value after a semicolon.
+/// ```
+/// use canrun::var;
+/// use canrun_collections::lmap::{lmap, LMap};
+///
+/// let open: LMap<i32, i32> = lmap!{1 => 2; var()};
+/// ```
 #[macro_export]
 macro_rules! lmap {
     ($($key:expr => $value:expr),*) => {
         {
+            // Not mutated when there are no entries, such as in `lmap! {; rest}`.
+            #[allow(unused_mut)]
             let mut map = $crate::lmap::LMap::new();
             $(map.insert($key, $value);)*
             map
         }
     };
+    ($($key:expr => $value:expr),*; $rest:expr) => {
+        {
+            let mut map = $crate::lmap::lmap!($($key => $value),*);
+            map.set_rest($rest);
+            map
+        }
+    };
 }
 
 #[doc(inline)]
@@ -298,4 +486,87 @@ mod tests {
             vec![unify(m, lmap!(x => 1, 1 => 2)), unify(m, lmap!(1 => 2))];
         util::assert_permutations_resolve_to(goals, (m, x), vec![]);
     }
+
+    #[test]
+    fn matches_variable_keys_by_value() {
+        let (x, y) = (var(), var());
+        let goal: Goal<Collections> = unify(lmap! {x => 1, y => 2}, lmap! {3 => 2, 4 => 1});
+        let results: Vec<_> = goal.query((x, y)).collect();
+        assert_eq!(results, vec![(4, 3)]);
+    }
+
+    #[test]
+    fn does_not_duplicate_matchings() {
+        let (x, y) = (var(), var());
+        let goal: Goal<Collections> = unify(lmap! {x => 1, y => 1}, lmap! {1 => 1, 2 => 1});
+        let mut results: Vec<_> = goal.query((x, y)).collect();
+        results.sort();
+        assert_eq!(results, vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn different_sizes_fail() {
+        let goal: Goal<Collections> = unify(lmap! {1 => 1, 2 => 2}, lmap! {1 => 1});
+        assert_eq!(goal.iter_resolved().count(), 0);
+    }
+
+    #[test]
+    fn open_map_binds_rest() {
+        let (x, r) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(lmap! {1 => x; r}, lmap! {1 => 2, 3 => 4})];
+        util::assert_permutations_resolve_to(goals, (x, r), vec![(2, hash_map!(3 => 4))]);
+    }
+
+    #[test]
+    fn open_map_with_variable_key() {
+        let (k, r) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(lmap! {k => 4; r}, lmap! {1 => 2, 3 => 4})];
+        util::assert_permutations_resolve_to(goals, (k, r), vec![(3, hash_map!(1 => 2))]);
+    }
+
+    #[test]
+    fn open_map_missing_key_fails() {
+        let r = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(lmap! {5 => 2; r}, lmap! {1 => 2})];
+        util::assert_permutations_resolve_to(goals, r, vec![]);
+    }
+
+    #[test]
+    fn open_map_merges_variable_keys() {
+        let (x, r) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(lmap! {x => 1, 1 => 1; r}, lmap! {1 => 1})];
+        util::assert_permutations_resolve_to(
+            goals,
+            (x, r),
+            vec![(1, std::collections::HashMap::new())],
+        );
+
+        let goals: Vec<Goal<Collections>> = vec![unify(
+            lmap! {x => 1, 1 => 1, 2 => 2; r},
+            lmap! {1 => 1, 3 => 3},
+        )];
+        util::assert_permutations_resolve_to(goals, (x, r), vec![]);
+    }
+
+    #[test]
+    fn two_open_maps_share_remainder() {
+        let (m, a, b) = (var(), var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            unify(m, lmap! {1 => 2; a}),
+            unify(m, lmap! {3 => 4; b}),
+            unify(m, lmap! {1 => 2, 3 => 4, 5 => 6}),
+        ];
+        util::assert_permutations_resolve_to(
+            goals,
+            (a, b),
+            vec![(hash_map!(3 => 4, 5 => 6), hash_map!(1 => 2, 5 => 6))],
+        );
+    }
+
+    #[test]
+    fn open_map_does_not_reify() {
+        let m = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(m, lmap! {1 => 2; var()})];
+        util::assert_permutations_resolve_to(goals, m, vec![]);
+    }
 }
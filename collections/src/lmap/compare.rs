@@ -1,5 +1,9 @@
 use super::{unify_entries, LMap};
-use canrun::{custom, project_2, DomainType, Goal, IntoVal, UnifyIn};
+use canrun::state::{
+    constraints::{resolve_2, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::{DomainType, Goal, IntoVal, UnifyIn, Val};
 use std::fmt::Debug;
 use std::hash::Hash;
 
@@ -8,6 +12,8 @@ use std::hash::Hash;
 /// This means that all of the keys in `a` unify with keys in `b` AND the
 /// corresponding values also unify. This is the opposite of [`is_superset`].
 ///
+/// If either map is open, the goal waits for its rest to be resolved.
+///
 /// # Example:
 /// ```
 /// use canrun::{var, Goal};
@@ -27,8 +33,9 @@ where
     B: IntoVal<LMap<K, V>>,
     D: DomainType<'a, LMap<K, V>> + DomainType<'a, K> + DomainType<'a, V> + 'a,
 {
-    project_2(a, b, |a, b| {
-        custom(move |state| unify_entries(state, a.clone(), b.clone()))
+    Goal::constraint(Subset {
+        a: a.into_val(),
+        b: b.into_val(),
     })
 }
 
@@ -59,12 +66,32 @@ where
     is_subset(b, a)
 }
 
+#[derive(Debug)]
+struct Subset<K: Eq + Hash + Debug, V: Debug> {
+    a: Val<LMap<K, V>>,
+    b: Val<LMap<K, V>>,
+}
+
+impl<'a, K, V, D> Constraint<'a, D> for Subset<K, V>
+where
+    K: Debug + Eq + Hash + UnifyIn<'a, D> + 'a,
+    V: Debug + UnifyIn<'a, D> + 'a,
+    D: DomainType<'a, LMap<K, V>> + DomainType<'a, K> + DomainType<'a, V> + 'a,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let (a, b) = resolve_2(&self.a, &self.b, state)?;
+        a.watch_rest(state)?;
+        b.watch_rest(state)?;
+        Ok(Box::new(move |state| unify_entries(state, a, b)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{is_subset, is_superset};
     use crate::example::Collections;
     use crate::lmap;
-    use canrun::{var, Goal, IterResolved};
+    use canrun::{unify, util, var, Goal, IterResolved};
 
     #[test]
     fn is_subset_should_succeed_on() {
@@ -99,6 +126,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn is_subset_of_open_map() {
+        let (x, r) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            is_subset(lmap! {1 => x}, lmap! {; r}),
+            unify(r, lmap! {1 => 2}),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![2]);
+
+        let goals: Vec<Goal<Collections>> = vec![
+            is_subset(lmap! {1 => x, 3 => 4}, lmap! {3 => 4; r}),
+            unify(r, lmap! {1 => 2}),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![2]);
+
+        let goals: Vec<Goal<Collections>> = vec![
+            is_subset(lmap! {1 => x}, lmap! {3 => 4; r}),
+            unify(r, lmap! {5 => 6}),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn open_subset_waits_for_rest() {
+        let (x, r) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            is_subset(lmap! {1 => 2; r}, lmap! {1 => 2, 3 => x}),
+            unify(r, lmap! {3 => 4}),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+
+        let goals: Vec<Goal<Collections>> = vec![
+            is_superset(lmap! {1 => x}, lmap! {1 => 2; r}),
+            unify(r, lmap! {3 => 4}),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
     #[test]
     fn is_superset_should_succeed_on() {
         let x = var();
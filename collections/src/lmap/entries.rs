@@ -0,0 +1,170 @@
+use crate::lmap::LMap;
+use crate::lvec::LVec;
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Create a [`Goal`](canrun::goal) that relates an [`LMap`] to an
+/// [`LVec`] of its keys.
+///
+/// The keys are given in ascending order so that the results don't depend on
+/// the internal order of the map. The goal waits for the map and every key in
+/// it to be resolved, including the entries in the rest of an open map.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lmap::{self, lmap};
+/// use canrun_collections::example::Collections;
+///
+/// let ks = var();
+/// let goal: Goal<Collections> = lmap::keys(lmap! {3 => 4, 1 => 2}, ks);
+/// let results: Vec<_> = goal.query(ks).collect();
+/// assert_eq!(results, vec![vec![1, 3]]);
+/// ```
+pub fn keys<'a, K, V, MV, LV, D>(map: MV, keys: LV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LMap<K, V>>,
+    LV: IntoVal<LVec<K>>,
+    LVec<K>: UnifyIn<'a, D>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>> + DomainType<'a, LVec<K>>,
+{
+    Goal::constraint(Keys {
+        map: map.into_val(),
+        keys: keys.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that relates an [`LMap`] to an
+/// [`LVec`] of its values.
+///
+/// The values are given in ascending order of their keys, which lines them up
+/// with the results of [`keys`]. The goal waits for the map and every key in
+/// it to be resolved.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lmap::{self, lmap};
+/// use canrun_collections::example::Collections;
+///
+/// let vs = var();
+/// let goal: Goal<Collections> = lmap::values(lmap! {3 => 4, 1 => 2}, vs);
+/// let results: Vec<_> = goal.query(vs).collect();
+/// assert_eq!(results, vec![vec![2, 4]]);
+/// ```
+pub fn values<'a, K, V, MV, LV, D>(map: MV, values: LV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LMap<K, V>>,
+    LV: IntoVal<LVec<V>>,
+    LVec<V>: UnifyIn<'a, D>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>> + DomainType<'a, LVec<V>>,
+{
+    Goal::constraint(Values {
+        map: map.into_val(),
+        values: values.into_val(),
+    })
+}
+
+/// The entries of a map with resolved keys, sorted by key.
+fn sorted_entries<K, V>(map: &LMap<K, V>) -> Vec<(&Val<K>, &Val<V>)>
+where
+    K: Eq + Hash + Ord + Debug,
+    V: Debug,
+{
+    let mut entries: Vec<_> = map.map.iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.resolved().ok().cmp(&b.resolved().ok()));
+    entries
+}
+
+#[derive(Debug)]
+struct Keys<K: Eq + Hash + Debug, V: Debug> {
+    map: Val<LMap<K, V>>,
+    keys: Val<LVec<K>>,
+}
+
+impl<'a, K, V, D> Constraint<'a, D> for Keys<K, V>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    LVec<K>: UnifyIn<'a, D>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>> + DomainType<'a, LVec<K>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let map = resolve_1(&self.map, state)?;
+        map.watch_entries(state)?;
+        let keys = self.keys.clone();
+        Ok(Box::new(move |state| {
+            let (state, map) = map.resolve_in(state)?;
+            let found: LVec<K> = sorted_entries(&map).into_iter().map(|(k, _)| k).into();
+            state.unify(&found.into_val(), &keys)
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct Values<K: Eq + Hash + Debug, V: Debug> {
+    map: Val<LMap<K, V>>,
+    values: Val<LVec<V>>,
+}
+
+impl<'a, K, V, D> Constraint<'a, D> for Values<K, V>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Ord + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    LVec<V>: UnifyIn<'a, D>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>> + DomainType<'a, LVec<V>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let map = resolve_1(&self.map, state)?;
+        map.watch_entries(state)?;
+        let values = self.values.clone();
+        Ok(Box::new(move |state| {
+            let (state, map) = map.resolve_in(state)?;
+            let found: LVec<V> = sorted_entries(&map).into_iter().map(|(_, v)| v).into();
+            state.unify(&found.into_val(), &values)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::{lmap, lvec};
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn keys_wait_for_variable_keys() {
+        let (k, ks) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![lmap::keys(lmap! {k => 1, 2 => 3}, ks), unify(k, 5)];
+        util::assert_permutations_resolve_to(goals, ks, vec![vec![2, 5]]);
+    }
+
+    #[test]
+    fn values_bind_unresolved_values() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lmap::values(lmap! {1 => x, 2 => 3}, lvec![4, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+    }
+
+    #[test]
+    fn merged_keys_are_listed_once() {
+        let (k, ks) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![lmap::keys(lmap! {k => 1, 2 => 1}, ks), unify(k, 2)];
+        util::assert_permutations_resolve_to(goals, ks, vec![vec![2]]);
+    }
+}
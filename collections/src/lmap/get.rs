@@ -0,0 +1,184 @@
+use crate::lmap::LMap;
+use canrun::goal::{all, unify, Goal};
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{var, IntoVal, LVar, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Create a [`Goal`](canrun::goal) that relates a key in an [`LMap`] to its
+/// value.
+///
+/// The goal waits for the map to be resolved. If the key is found directly,
+/// its value is unified. Otherwise the state is forked for each entry that the
+/// key and value could unify with, just like when unifying maps with unbound
+/// keys. If the map is open, the entry may also be added to its rest.
+///
+/// # Examples:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lmap::{self, lmap};
+/// use canrun_collections::example::Collections;
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lmap::get(lmap! {1 => 2, 3 => 4}, 3, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![4]);
+/// ```
+///
+/// ```
+/// # use canrun::{var, Goal};
+/// # use canrun_collections::lmap::{self, lmap};
+/// # use canrun_collections::example::Collections;
+/// let k = var();
+/// let goal: Goal<Collections> = lmap::get(lmap! {1 => 2, 3 => 4}, k, 4);
+/// let results: Vec<_> = goal.query(k).collect();
+/// assert_eq!(results, vec![3]);
+/// ```
+pub fn get<'a, K, V, MV, KV, VV, D>(map: MV, key: KV, value: VV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LMap<K, V>>,
+    KV: IntoVal<K>,
+    VV: IntoVal<V>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
+{
+    Goal::constraint(Get {
+        map: map.into_val(),
+        key: key.into_val(),
+        value: value.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that succeeds if a key can be found in an
+/// [`LMap`].
+///
+/// This is [`get`] with an unbound value.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lmap::{self, lmap};
+/// use canrun_collections::example::Collections;
+///
+/// let k = var();
+/// let goal: Goal<Collections> = lmap::contains_key(lmap! {1 => 2}, k);
+/// let results: Vec<_> = goal.query(k).collect();
+/// assert_eq!(results, vec![1]);
+/// ```
+pub fn contains_key<'a, K, V, MV, KV, D>(map: MV, key: KV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LMap<K, V>>,
+    KV: IntoVal<K>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
+{
+    let value: LVar<V> = var();
+    get(map, key, value)
+}
+
+#[derive(Debug)]
+struct Get<K: Eq + Hash + Debug, V: Debug> {
+    map: Val<LMap<K, V>>,
+    key: Val<K>,
+    value: Val<V>,
+}
+
+impl<'a, K, V, D> Constraint<'a, D> for Get<K, V>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let map = resolve_1(&self.map, state)?;
+        let key = state.resolve_val(&self.key).clone();
+        let value = self.value.clone();
+        Ok(Box::new(move |state| {
+            let (state, map) = map.resolve_in(state)?;
+            if let Some(found) = map.map.get(&key) {
+                return state.unify(found, &value);
+            }
+            let mut goals: Vec<Goal<D>> = map
+                .map
+                .iter()
+                .map(|(k, v)| {
+                    all![
+                        unify::<K, _, _, D>(key.clone(), k.clone()),
+                        unify::<V, _, _, D>(value.clone(), v.clone()),
+                    ]
+                })
+                .collect();
+            // An open map may also have the entry somewhere in its rest.
+            if let Some(rest) = &map.rest {
+                let mut entry = LMap::new();
+                entry.insert(key, value);
+                entry.set_rest(var());
+                goals.push(unify(rest.clone(), entry));
+            }
+            Goal::any(goals).apply(state)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lmap;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn get_waits_for_map() {
+        let (m, x) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![lmap::get(m, 1, x), unify(m, lmap! {1 => 2, 3 => 4})];
+        util::assert_permutations_resolve_to(goals, x, vec![2]);
+    }
+
+    #[test]
+    fn get_missing_key() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lmap::get(lmap! {1 => 2}, 3, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn get_with_variable_key() {
+        let (k, v) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![lmap::get(lmap! {1 => 2, 3 => 4}, k, v)];
+        util::assert_permutations_resolve_to(goals, (k, v), vec![(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn get_from_variable_map_key() {
+        let (k, v) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![lmap::get(lmap! {k => 2}, 1, v), unify(k, 1)];
+        util::assert_permutations_resolve_to(goals, v, vec![2]);
+    }
+
+    #[test]
+    fn get_from_open_map() {
+        let (m, rest, x) = (var(), var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            unify(m, lmap! {1 => 2; rest}),
+            lmap::get(m, 3, x),
+            unify(rest, lmap! {3 => 4}),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+    }
+
+    #[test]
+    fn contains_key_fails_when_missing() {
+        let k = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(k, 2), lmap::contains_key(lmap! {1 => 2}, k)];
+        util::assert_permutations_resolve_to(goals, k, vec![]);
+    }
+}
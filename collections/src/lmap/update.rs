@@ -0,0 +1,206 @@
+use crate::lmap::LMap;
+use canrun::goal::{all, unify, Goal};
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Create a [`Goal`](canrun::goal) that relates an [`LMap`] to a copy with a
+/// key set to a new value.
+///
+/// An existing value for the key is replaced. The goal waits for the map to be
+/// resolved, including the rest of an open map. If the key is found directly,
+/// its value is replaced. Otherwise the state is forked for each entry the key
+/// could unify with, and once more for the key being added as a new entry.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lmap::{self, lmap};
+/// use canrun_collections::example::Collections;
+/// use std::collections::HashMap;
+///
+/// let m = var();
+/// let goal: Goal<Collections> = lmap::insert(lmap! {1 => 2}, 3, 4, m);
+/// let results: Vec<_> = goal.query(m).collect();
+/// assert_eq!(results, vec![vec![(1, 2), (3, 4)].into_iter().collect::<HashMap<_, _>>()]);
+/// ```
+pub fn insert<'a, K, V, MV, KV, VV, RV, D>(map: MV, key: KV, value: VV, updated: RV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LMap<K, V>>,
+    KV: IntoVal<K>,
+    VV: IntoVal<V>,
+    RV: IntoVal<LMap<K, V>>,
+    LMap<K, V>: UnifyIn<'a, D>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
+{
+    Goal::constraint(Update {
+        map: map.into_val(),
+        key: key.into_val(),
+        value: Some(value.into_val()),
+        updated: updated.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that relates an [`LMap`] to a copy without
+/// a key.
+///
+/// Like [`insert`], this waits for the map to be resolved and forks for each
+/// entry an unbound key could unify with. The goal fails if the key cannot be
+/// found in the map, so that `remove` is the inverse of inserting a new key.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lmap::{self, lmap};
+/// use canrun_collections::example::Collections;
+/// use std::collections::HashMap;
+///
+/// let m = var();
+/// let goal: Goal<Collections> = lmap::remove(lmap! {1 => 2, 3 => 4}, 3, m);
+/// let results: Vec<_> = goal.query(m).collect();
+/// assert_eq!(results, vec![vec![(1, 2)].into_iter().collect::<HashMap<_, _>>()]);
+/// ```
+pub fn remove<'a, K, V, MV, KV, RV, D>(map: MV, key: KV, updated: RV) -> Goal<'a, D>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    MV: IntoVal<LMap<K, V>>,
+    KV: IntoVal<K>,
+    RV: IntoVal<LMap<K, V>>,
+    LMap<K, V>: UnifyIn<'a, D>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
+{
+    Goal::constraint(Update {
+        map: map.into_val(),
+        key: key.into_val(),
+        value: None,
+        updated: updated.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Update<K: Eq + Hash + Debug, V: Debug> {
+    map: Val<LMap<K, V>>,
+    key: Val<K>,
+    // `None` removes the key.
+    value: Option<Val<V>>,
+    updated: Val<LMap<K, V>>,
+}
+
+impl<'a, K, V, D> Constraint<'a, D> for Update<K, V>
+where
+    K: UnifyIn<'a, D> + Eq + Hash + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    LMap<K, V>: UnifyIn<'a, D>,
+    D: DomainType<'a, K> + DomainType<'a, V> + DomainType<'a, LMap<K, V>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let map = resolve_1(&self.map, state)?;
+        map.watch_rest(state)?;
+        let key = state.resolve_val(&self.key).clone();
+        let value = self.value.clone();
+        let updated = self.updated.clone();
+        Ok(Box::new(move |state| {
+            let (state, map) = map.resolve_in(state)?;
+            let replace = |found: &Val<K>| {
+                let mut map = map.map.clone();
+                map.remove(found);
+                if let Some(value) = &value {
+                    map.insert(found.clone(), value.clone());
+                }
+                LMap { map, rest: None }
+            };
+            if map.map.contains_key(&key) {
+                return state.unify(&replace(&key).into_val(), &updated);
+            }
+            let mut goals: Vec<Goal<D>> = map
+                .map
+                .keys()
+                .map(|k| {
+                    all![
+                        unify::<K, _, _, D>(key.clone(), k.clone()),
+                        unify::<LMap<K, V>, _, _, D>(updated.clone(), replace(k)),
+                    ]
+                })
+                .collect();
+            // An insert may also add the key as a new entry.
+            if let Some(value) = &value {
+                let mut map = map.map.clone();
+                map.insert(key.clone(), value.clone());
+                goals.push(unify(updated.clone(), LMap { map, rest: None }));
+            }
+            Goal::any(goals).apply(state)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lmap;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn insert_replaces_value() {
+        let (m, x) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![lmap::insert(lmap! {1 => 2}, 1, 3, m), lmap::get(m, 1, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![3]);
+    }
+
+    #[test]
+    fn insert_forks_on_unbound_keys() {
+        let (k, x) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lmap::insert(lmap! {k => 2}, 1, 3, lmap! {1 => x}),
+            unify(k, 1),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![3]);
+    }
+
+    #[test]
+    fn insert_unbound_key() {
+        let (k, m) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lmap::insert(lmap! {1 => 2, 3 => 4}, k, 5, m),
+            lmap::get(m, 1, 5),
+        ];
+        util::assert_permutations_resolve_to(goals, k, vec![1]);
+    }
+
+    #[test]
+    fn remove_from_updated() {
+        let (k, x) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lmap::remove(lmap! {1 => 2, 3 => x}, k, lmap! {1 => 2}),
+            unify(k, 3),
+            unify(x, 4),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+    }
+
+    #[test]
+    fn remove_forks_on_unbound_key() {
+        let k = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![lmap::remove(lmap! {1 => 2, 3 => 4}, k, lmap! {1 => 2})];
+        util::assert_permutations_resolve_to(goals, k, vec![3]);
+    }
+
+    #[test]
+    fn remove_missing_key_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![lmap::remove(lmap! {1 => 2}, 3, lmap! {1 => 2}), unify(x, 1)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
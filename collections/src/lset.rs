@@ -0,0 +1,254 @@
+//! A [`HashSet`](std::collections::HashSet)-like data structure with
+//! [`LVar`](canrun::value::LVar) values.
+use crate::matching::unify_unmatched;
+use canrun::state::constraints::VarWatch;
+use canrun::{DomainType, IntoVal, ReifyIn, ResolvedState, State, UnifyIn, Val};
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+mod algebra;
+mod member;
+mod subset;
+
+pub use algebra::{disjoint, intersection, union};
+pub use member::member;
+pub use subset::subset;
+
+/// A [`HashSet`](std::collections::HashSet)-like data structure with
+/// [`LVar`](canrun::value::LVar) values.
+///
+/// Two sets unify when every item in each set unifies with some item in the
+/// other, so `{x, 1}` and `{1, 2}` unify with `x = 2`. Items that resolve to the
+/// same value are collapsed into one.
+#[derive(Debug, Clone)]
+pub struct LSet<T: Eq + Hash + Debug> {
+    set: HashSet<Val<T>>,
+}
+
+impl<T: Eq + Hash + Debug> LSet<T> {
+    /// Create a new [`LSet`] value.
+    ///
+    /// You may also be interested in the [`lset!`] macro.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_collections::lset::LSet;
+    ///
+    /// let set: LSet<i32> = LSet::new();
+    /// ```
+    pub fn new() -> Self {
+        LSet {
+            set: HashSet::new(),
+        }
+    }
+
+    /// Add an item to an existing [`LSet`].
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_collections::lset::LSet;
+    ///
+    /// let mut set: LSet<i32> = LSet::new();
+    /// set.insert(1);
+    /// ```
+    pub fn insert<Ti>(&mut self, item: Ti)
+    where
+        Ti: IntoVal<T>,
+    {
+        self.set.insert(item.into_val());
+    }
+
+    /// Get the number of items in the [`LSet`].
+    ///
+    /// Unbound items that later resolve to the same value are counted
+    /// separately.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_collections::lset::{LSet, lset};
+    ///
+    /// let set: LSet<i32> = lset![1, 2, 1];
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if the [`LSet`] has no items.
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    fn resolve_in<'a, D>(&self, state: &State<'a, D>) -> Rc<Self>
+    where
+        D: DomainType<'a, T>,
+    {
+        let set = self
+            .set
+            .iter()
+            .map(|item| state.resolve_val(item).clone())
+            .collect();
+        Rc::new(LSet { set })
+    }
+
+    /// Resolve every item, or watch the first one that is still unbound.
+    fn resolve_items<'a, D>(&self, state: &State<'a, D>) -> Result<HashSet<Val<T>>, VarWatch>
+    where
+        D: DomainType<'a, T>,
+    {
+        self.set
+            .iter()
+            .map(|item| match state.resolve_val(item) {
+                Val::Var(var) => Err(VarWatch::one(*var)),
+                resolved => Ok(resolved.clone()),
+            })
+            .collect()
+    }
+}
+
+impl<T: Eq + Hash + Debug> Default for LSet<T> {
+    fn default() -> Self {
+        LSet::new()
+    }
+}
+
+impl<'a, T, D> UnifyIn<'a, D> for LSet<T>
+where
+    T: UnifyIn<'a, D> + Eq + Hash + fmt::Debug + 'a,
+    D: DomainType<'a, T> + DomainType<'a, Self>,
+{
+    fn unify_resolved(state: State<'a, D>, a: Rc<Self>, b: Rc<Self>) -> Option<State<'a, D>> {
+        let a = a.resolve_in(&state);
+        let b = b.resolve_in(&state);
+        // Items that are already identical match without any searching.
+        let unmatched = |from: &LSet<T>, to: &LSet<T>| -> Vec<Val<T>> {
+            from.set
+                .iter()
+                .filter(|item| !to.set.contains(item))
+                .cloned()
+                .collect()
+        };
+        let items = |set: &LSet<T>| set.set.iter().cloned().collect();
+        unify_unmatched(
+            state,
+            (unmatched(&a, &b), items(&a)),
+            (unmatched(&b, &a), items(&b)),
+        )
+    }
+}
+
+impl<'a, D, Tv, Tr> ReifyIn<'a, D> for LSet<Tv>
+where
+    D: DomainType<'a, Tv> + 'a,
+    Tv: ReifyIn<'a, D, Reified = Tr> + Eq + Hash + Debug,
+    Tr: Eq + Hash,
+{
+    type Reified = HashSet<Tr>;
+    fn reify_in(&self, state: &ResolvedState<D>) -> Option<Self::Reified> {
+        self.set.iter().map(|item| state.reify(item)).collect()
+    }
+}
+
+impl<T, I, IV> From<I> for LSet<T>
+where
+    T: Eq + Hash + Debug,
+    IV: IntoVal<T>,
+    I: IntoIterator<Item = IV>,
+{
+    fn from(items: I) -> Self {
+        LSet {
+            set: items.into_iter().map(|item| item.into_val()).collect(),
+        }
+    }
+}
+
+/// Create an [`LSet`](crate::lset::LSet) with automatic value [`IntoVal`
+/// wrapping](canrun::value::IntoVal).
+///
+/// The primary benefit is that it allows freely mixing resolved values and
+/// [`LVar`s](canrun::value::LVar).
+///
+/// # Example:
+/// ```
+/// use canrun::var;
+/// use canrun_collections::lset::{lset, LSet};
+///
+/// let x = var();
+/// let set: LSet<i32> = lset![x, 1, 2];
+/// ```
+#[macro_export]
+macro_rules! lset {
+    ($($item:expr),* $(,)?) => {
+        {
+            let mut set = $crate::lset::LSet::new();
+            $(set.insert($item);)*
+            set
+        }
+    };
+}
+
+#[doc(inline)]
+pub use lset;
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use canrun::{unify, util, var, Goal};
+    use std::collections::HashSet;
+
+    fn hash_set(items: &[i32]) -> HashSet<i32> {
+        items.iter().copied().collect()
+    }
+
+    #[test]
+    fn succeeds_in_any_order() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, lset![1, 2]), unify(x, lset![2, 1])];
+        util::assert_permutations_resolve_to(goals, x, vec![hash_set(&[1, 2])]);
+    }
+
+    #[test]
+    fn fails_with_different() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, lset![1, 2]), unify(x, lset![1, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn binds_missing_item() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(lset![x, 1], lset![1, 2])];
+        util::assert_permutations_resolve_to(goals, x, vec![2]);
+    }
+
+    #[test]
+    fn variables_on_both_sides() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(lset![x, 2], lset![1, y])];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn duplicates_collapse() {
+        let (s, x) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(s, lset![1, x]), unify(s, lset![1])];
+        util::assert_permutations_resolve_to(goals, x, vec![1]);
+    }
+
+    #[test]
+    fn no_duplicate_answers() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(lset![x, y, 1], lset![1, 2])];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(2, 2), (2, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn one_to_one_without_duplicates() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![unify(lset![x, y], lset![1, 2])];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(1, 2), (2, 1)]);
+    }
+}
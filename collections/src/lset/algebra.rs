@@ -0,0 +1,196 @@
+use crate::lset::LSet;
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{resolve_2, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Create a [`Goal`](canrun::goal) that unifies an `LSet<T>` with the union of
+/// two other `LSet`s.
+///
+/// The goal waits for both inputs to be resolved. Their items may still
+/// contain unbound variables, which are matched up through set unification.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lset, example::Collections};
+/// use std::collections::HashSet;
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lset::union(lset![1, 2], lset![2, 3], x);
+/// let results: Vec<HashSet<i32>> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![1, 2, 3].into_iter().collect()]);
+/// ```
+pub fn union<'a, T, AV, BV, CV, D>(a: AV, b: BV, union: CV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + Eq + Hash + 'a,
+    AV: IntoVal<LSet<T>>,
+    BV: IntoVal<LSet<T>>,
+    CV: IntoVal<LSet<T>>,
+    LSet<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LSet<T>>,
+{
+    Goal::constraint(Combine {
+        a: a.into_val(),
+        b: b.into_val(),
+        result: union.into_val(),
+        op: Op::Union,
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that unifies an `LSet<T>` with the
+/// intersection of two other `LSet`s.
+///
+/// Deciding whether an item is shared requires knowing its value, so the goal
+/// waits for every item in both inputs to be resolved.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lset, example::Collections};
+/// use std::collections::HashSet;
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lset::intersection(lset![1, 2], lset![2, 3], x);
+/// let results: Vec<HashSet<i32>> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![2].into_iter().collect()]);
+/// ```
+pub fn intersection<'a, T, AV, BV, CV, D>(a: AV, b: BV, intersection: CV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + Eq + Hash + 'a,
+    AV: IntoVal<LSet<T>>,
+    BV: IntoVal<LSet<T>>,
+    CV: IntoVal<LSet<T>>,
+    LSet<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LSet<T>>,
+{
+    Goal::constraint(Combine {
+        a: a.into_val(),
+        b: b.into_val(),
+        result: intersection.into_val(),
+        op: Op::Intersection,
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that succeeds if two `LSet`s have no items
+/// in common.
+///
+/// Like [`intersection`], this waits for every item in both sets to be
+/// resolved.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var, all, unify};
+/// use canrun_collections::{lset, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = all![
+///     lset::member(x, lset![1, 2, 3]),
+///     lset::disjoint(lset![x], lset![1, 2]),
+/// ];
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![3]);
+/// ```
+pub fn disjoint<'a, T, AV, BV, D>(a: AV, b: BV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + Eq + Hash + 'a,
+    AV: IntoVal<LSet<T>>,
+    BV: IntoVal<LSet<T>>,
+    LSet<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LSet<T>>,
+{
+    Goal::constraint(Combine {
+        a: a.into_val(),
+        b: b.into_val(),
+        result: LSet::new().into_val(),
+        op: Op::Intersection,
+    })
+}
+
+#[derive(Debug)]
+enum Op {
+    Union,
+    Intersection,
+}
+
+#[derive(Debug)]
+struct Combine<T: Eq + Hash + Debug> {
+    a: Val<LSet<T>>,
+    b: Val<LSet<T>>,
+    result: Val<LSet<T>>,
+    op: Op,
+}
+
+impl<'a, T, D> Constraint<'a, D> for Combine<T>
+where
+    T: UnifyIn<'a, D> + Eq + Hash + 'a,
+    LSet<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LSet<T>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let (a, b) = resolve_2(&self.a, &self.b, state)?;
+        let combined = match self.op {
+            Op::Union => a.set.union(&b.set).cloned().collect(),
+            Op::Intersection => {
+                let (a, b) = (a.resolve_items(state)?, b.resolve_items(state)?);
+                a.intersection(&b).cloned().collect()
+            }
+        };
+        let combined = LSet { set: combined }.into_val();
+        let result = self.result.clone();
+        Ok(Box::new(move |state| state.unify(&combined, &result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lset;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+    use std::collections::HashSet;
+
+    fn hash_set(items: &[i32]) -> HashSet<i32> {
+        items.iter().copied().collect()
+    }
+
+    #[test]
+    fn union_with_variables() {
+        let (x, s) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lset::union(lset![1, x], lset![2], s),
+            unify(s, lset![1, 2, 3]),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![3]);
+    }
+
+    #[test]
+    fn intersection_waits_for_items() {
+        let (x, s) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![lset::intersection(lset![1, x], lset![2, 3], s), unify(x, 3)];
+        util::assert_permutations_resolve_to(goals, s, vec![hash_set(&[3])]);
+    }
+
+    #[test]
+    fn disjoint_fails_with_shared_items() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, 2), lset::disjoint(lset![1, x], lset![2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn disjoint_succeeds() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, 4), lset::disjoint(lset![1, x], lset![2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::lset::LSet;
+use canrun::goal::{unify, Goal};
+use canrun::state::{
+    constraints::{resolve_1, Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Create a [`Goal`](canrun::goal) that attempts to unify a `Val<T>` with any
+/// of the items in an `LSet<T>`.
+///
+/// This goal will fork the state for each match found.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lset, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lset::member(x, lset![1, 2]);
+/// let mut results: Vec<_> = goal.query(x).collect();
+/// results.sort();
+/// assert_eq!(results, vec![1, 2]);
+/// ```
+pub fn member<'a, T, IV, SV, D>(item: IV, set: SV) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + Eq + Hash + 'a,
+    IV: IntoVal<T>,
+    SV: IntoVal<LSet<T>>,
+    LSet<T>: UnifyIn<'a, D>,
+    D: DomainType<'a, T> + DomainType<'a, LSet<T>>,
+{
+    Goal::constraint(Member {
+        item: item.into_val(),
+        set: set.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Member<T: Eq + Hash + Debug> {
+    item: Val<T>,
+    set: Val<LSet<T>>,
+}
+
+impl<'a, T, D> Constraint<'a, D> for Member<T>
+where
+    T: UnifyIn<'a, D> + Eq + Hash,
+    D: DomainType<'a, T> + DomainType<'a, LSet<T>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let set = resolve_1(&self.set, state)?;
+        let goals: Vec<Goal<D>> = set
+            .set
+            .iter()
+            .map(|found| unify::<T, _, _, D>(self.item.clone(), found.clone()))
+            .collect();
+        Ok(Box::new(|state| Goal::any(goals).apply(state)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lset;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn member_of_resolved() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, 2), lset::member(x, lset![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![2]);
+    }
+
+    #[test]
+    fn not_a_member() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, 4), lset::member(x, lset![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,62 @@
+use super::LSet;
+use crate::matching::Cover;
+use canrun::{custom, project_2, DomainType, Goal, IntoVal, UnifyIn};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// Assert that every item in [`LSet`] `a` unifies with some item in [`LSet`]
+/// `b`.
+///
+/// # Example:
+/// ```
+/// use canrun::{var, Goal};
+/// use canrun_collections::lset::{lset, subset};
+/// use canrun_collections::example::Collections;
+///
+/// let x = var();
+/// let goal: Goal<Collections> = subset(lset![x, 1], lset![1, 3]);
+/// let mut results: Vec<_> = goal.query(x).collect();
+/// results.sort();
+/// assert_eq!(results, vec![1, 3]);
+/// ```
+pub fn subset<'a, T, A, B, D>(a: A, b: B) -> Goal<'a, D>
+where
+    T: Debug + Eq + Hash + UnifyIn<'a, D> + 'a,
+    A: IntoVal<LSet<T>>,
+    B: IntoVal<LSet<T>>,
+    D: DomainType<'a, LSet<T>> + DomainType<'a, T> + 'a,
+{
+    project_2(a, b, |a: Rc<LSet<T>>, b: Rc<LSet<T>>| {
+        custom(move |state| {
+            let (a, b) = (a.resolve_in(&state), b.resolve_in(&state));
+            state.fork(Rc::new(Cover {
+                from: a.set.iter().cloned().collect(),
+                to: b.set.iter().cloned().collect(),
+                leftover: None,
+            }))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lset;
+    use canrun::{util, var, Goal};
+
+    #[test]
+    fn subset_succeeds() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lset::subset(lset![1, x], lset![1, 2])];
+        util::assert_permutations_resolve_to(goals, x, vec![1, 2]);
+    }
+
+    #[test]
+    fn subset_fails() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![lset::subset(lset![1, 4], lset![1, 2]), canrun::unify(x, 1)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -1,9 +1,17 @@
 //! A [`Vec`]-like data structure with [`LVar`](canrun::value::LVar) values.
 
+mod append;
+mod length;
 mod member;
+mod nth;
+mod subsequence;
 mod subset;
 
+pub use append::{append, prefix, suffix};
+pub use length::{length, length_up_to};
 pub use member::member;
+pub use nth::{index_of, nth, set_nth};
+pub use subsequence::{permutation, sub_multiset, subsequence};
 pub use subset::subset;
 
 use canrun::{DomainType, IntoVal, ReifyIn, ResolvedState, State, UnifyIn, Val};
@@ -44,6 +52,19 @@ impl<V: Debug> LVec<V> {
         self.vec.len()
     }
 
+    /// Iterate over the values in the [`LVec`].
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun_collections::lvec::{LVec, lvec};
+    ///
+    /// let vec: LVec<i32> = lvec![1, 2];
+    /// assert_eq!(vec.iter().count(), 2);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &Val<V>> {
+        self.vec.iter()
+    }
+
     /// Add a value to an existing [`LVec`].
     ///
     /// # Example:
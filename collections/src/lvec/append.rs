@@ -0,0 +1,210 @@
+use crate::lvec::LVec;
+use canrun::goal::{all, unify, Goal};
+use canrun::state::{
+    constraints::{Constraint, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{var, IntoVal, LVar, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+
+/// Create a [`Goal`](canrun::goal) that relates two `LVec<T>`s to the result
+/// of appending them together.
+///
+/// Once the combined `LVec` is resolved, this goal will fork the state for
+/// each way it can be split in two. If both parts are resolved first, the
+/// combined `LVec` is unified with their concatenation. If none of them are
+/// resolved, the goal waits. Use [`length_up_to`](crate::lvec::length_up_to())
+/// to enumerate candidates in that case.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::append(lvec![1, 2], lvec![3], x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![1, 2, 3]]);
+/// ```
+///
+/// ```
+/// # use canrun::{Goal, var};
+/// # use canrun_collections::{lvec, example::Collections};
+/// let (x, y) = (var(), var());
+/// let goal: Goal<Collections> = lvec::append(x, y, lvec![1, 2]);
+/// let results: Vec<_> = goal.query((x, y)).collect();
+/// assert_eq!(results, vec![
+///     (vec![], vec![1, 2]),
+///     (vec![1], vec![2]),
+///     (vec![1, 2], vec![]),
+/// ]);
+/// ```
+pub fn append<'a, I, AV, BV, CV, D>(a: AV, b: BV, ab: CV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    AV: IntoVal<LVec<I>>,
+    BV: IntoVal<LVec<I>>,
+    CV: IntoVal<LVec<I>>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    Goal::constraint(Append {
+        a: a.into_val(),
+        b: b.into_val(),
+        ab: ab.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that succeeds if an `LVec<T>` is a prefix
+/// of another.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::prefix(x, lvec![1, 2]);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![], vec![1], vec![1, 2]]);
+/// ```
+pub fn prefix<'a, I, PV, CV, D>(prefix: PV, collection: CV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    PV: IntoVal<LVec<I>>,
+    CV: IntoVal<LVec<I>>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    let rest: LVar<LVec<I>> = var();
+    append(prefix, rest, collection)
+}
+
+/// Create a [`Goal`](canrun::goal) that succeeds if an `LVec<T>` is a suffix
+/// of another.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::suffix(x, lvec![1, 2]);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![1, 2], vec![2], vec![]]);
+/// ```
+pub fn suffix<'a, I, SV, CV, D>(suffix: SV, collection: CV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    SV: IntoVal<LVec<I>>,
+    CV: IntoVal<LVec<I>>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    let rest: LVar<LVec<I>> = var();
+    append(rest, suffix, collection)
+}
+
+#[derive(Debug)]
+struct Append<I: Debug> {
+    a: Val<LVec<I>>,
+    b: Val<LVec<I>>,
+    ab: Val<LVec<I>>,
+}
+
+impl<'a, I, D> Constraint<'a, D> for Append<I>
+where
+    I: UnifyIn<'a, D> + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        use canrun::value::Val::{Resolved, Var};
+        let a = state.resolve_val(&self.a).clone();
+        let b = state.resolve_val(&self.b).clone();
+        let ab = state.resolve_val(&self.ab).clone();
+        match (&a, &b, &ab) {
+            (_, _, Resolved(whole)) => {
+                let len = whole.len();
+                // A known part only leaves one place to split.
+                let splits: Vec<usize> = match (&a, &b) {
+                    (Resolved(a), _) if a.len() <= len => vec![a.len()],
+                    (_, Resolved(b)) if b.len() <= len => vec![len - b.len()],
+                    (Resolved(_), _) | (_, Resolved(_)) => vec![],
+                    _ => (0..=len).collect(),
+                };
+                let goals: Vec<Goal<D>> = splits
+                    .into_iter()
+                    .map(|at| {
+                        let head: LVec<I> = whole.vec[..at].into();
+                        let tail: LVec<I> = whole.vec[at..].into();
+                        all![unify(a.clone(), head), unify(b.clone(), tail)]
+                    })
+                    .collect();
+                Ok(Box::new(|state| Goal::any(goals).apply(state)))
+            }
+            (Resolved(a), Resolved(b), _) => {
+                let joined: LVec<I> = a.vec.iter().chain(b.vec.iter()).into();
+                Ok(Box::new(move |state| state.unify(&joined.into_val(), &ab)))
+            }
+            (Var(a), _, Var(ab)) => Err(VarWatch::two(*a, *ab)),
+            (_, Var(b), Var(ab)) => Err(VarWatch::two(*b, *ab)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lvec;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn joins_resolved_parts() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::append(lvec![1], lvec![2, 3], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn finds_missing_suffix() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::append(lvec![1], x, lvec![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![2, 3]]);
+    }
+
+    #[test]
+    fn finds_missing_prefix() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::append(x, lvec![3], lvec![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn mismatched_parts_fail() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::append(x, lvec![2], lvec![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn splits_with_unbound_items() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![lvec::append(x, lvec![y], lvec![1, 2]), unify(x, lvec![1])];
+        util::assert_permutations_resolve_to(goals, y, vec![2]);
+    }
+
+    #[test]
+    fn prefixes_and_suffixes() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![
+            lvec::prefix(x, lvec![1, 2, 1]),
+            lvec::suffix(x, lvec![1, 2, 1]),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![], vec![1], vec![1, 2, 1]]);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::lvec::LVec;
+use canrun::goal::{all, unify, Goal};
+use canrun::state::{
+    constraints::{Constraint, OneOfTwo, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{var, IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+
+/// Create a [`Goal`](canrun::goal) that relates an `LVec<T>` to its length.
+///
+/// If only the length is known, the `LVec` is unified with a new `LVec` of
+/// that many unbound items. If neither is known the goal waits. See
+/// [`length_up_to`] for a version that enumerates lengths.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let n = var();
+/// let goal: Goal<Collections> = lvec::length(lvec![1, 2, 3], n);
+/// let results: Vec<_> = goal.query(n).collect();
+/// assert_eq!(results, vec![3]);
+/// ```
+///
+/// ```
+/// # use canrun::{Goal, var, all};
+/// # use canrun_collections::{lvec, example::Collections};
+/// let (x, y) = (var(), var());
+/// let goal: Goal<Collections> = all![
+///     lvec::length(x, 2),
+///     lvec::append(x, lvec![3], y),
+///     lvec::prefix(lvec![1, 2], y),
+/// ];
+/// let results: Vec<_> = goal.query(y).collect();
+/// assert_eq!(results, vec![vec![1, 2, 3]]);
+/// ```
+pub fn length<'a, I, CV, NV, D>(collection: CV, len: NV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    CV: IntoVal<LVec<I>>,
+    NV: IntoVal<usize>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    Goal::constraint(Length {
+        collection: collection.into_val(),
+        len: len.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that relates an `LVec<T>` to its length,
+/// which may not be greater than `max`.
+///
+/// Unlike [`length`], this will fork the state for each possible length from
+/// `0` to `max` when the `LVec` is not resolved. This is useful for generating
+/// candidates for relations like [`append`](crate::lvec::append()) when none of
+/// the `LVec`s are known.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let n = var();
+/// let goal: Goal<Collections> = lvec::length_up_to(var(), n, 2);
+/// let results: Vec<_> = goal.query(n).collect();
+/// assert_eq!(results, vec![0, 1, 2]);
+/// ```
+pub fn length_up_to<'a, I, CV, NV, D>(collection: CV, len: NV, max: usize) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    CV: IntoVal<LVec<I>>,
+    NV: IntoVal<usize>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    let collection = collection.into_val();
+    let len = len.into_val();
+    Goal::any((0..=max).map(|n| all![unify(len.clone(), n), length(collection.clone(), n),]))
+}
+
+#[derive(Debug)]
+struct Length<I: Debug> {
+    collection: Val<LVec<I>>,
+    len: Val<usize>,
+}
+
+impl<'a, I, D> Constraint<'a, D> for Length<I>
+where
+    I: UnifyIn<'a, D> + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        match OneOfTwo::resolve(&self.collection, &self.len, state)? {
+            OneOfTwo::A(collection, len) => {
+                let actual = collection.len().into_val();
+                Ok(Box::new(move |state| state.unify(&actual, &len)))
+            }
+            OneOfTwo::B(collection, len) => {
+                let fresh: LVec<I> = (0..*len).map(|_| var::<I>()).into();
+                Ok(Box::new(move |state| {
+                    state.unify(&fresh.into_val(), &collection)
+                }))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lvec;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn measures_resolved() {
+        let n = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::length(lvec![1, 2], n)];
+        util::assert_permutations_resolve_to(goals, n, vec![2]);
+    }
+
+    #[test]
+    fn mismatched_length() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![unify(x, lvec![1, 2]), lvec::length(x, 3)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn shapes_unresolved() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> =
+            vec![lvec::length(x, 2), unify(x, lvec![1, y]), unify(y, 5)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 5]]);
+    }
+
+    #[test]
+    fn enumerates_up_to_bound() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lvec::length_up_to(x, var(), 2),
+            lvec::length_up_to(y, var(), 2),
+            lvec::append(x, y, lvec![1, 2, 3]),
+        ];
+        util::assert_permutations_resolve_to(
+            goals,
+            (x, y),
+            vec![(vec![1], vec![2, 3]), (vec![1, 2], vec![3])],
+        );
+    }
+}
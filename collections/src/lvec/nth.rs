@@ -0,0 +1,265 @@
+use crate::lvec::LVec;
+use canrun::goal::{all, unify, Goal};
+use canrun::state::{
+    constraints::{resolve_1, Constraint, OneOfTwo, ResolveFn, VarWatch},
+    State,
+};
+use canrun::value::{var, IntoVal, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+
+/// Create a [`Goal`](canrun::goal) that relates a (zero based) index in an
+/// `LVec<T>` to the item at that position.
+///
+/// The goal waits for the `LVec` to be resolved. If the index is not known,
+/// the state is forked for each position in the `LVec`. An index that is out of
+/// bounds fails.
+///
+/// # Examples:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::nth(1, lvec![1, 2, 3], x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![2]);
+/// ```
+///
+/// ```
+/// # use canrun::{Goal, var};
+/// # use canrun_collections::{lvec, example::Collections};
+/// let (i, x) = (var(), var());
+/// let goal: Goal<Collections> = lvec::nth(i, lvec![1, 2], x);
+/// let results: Vec<_> = goal.query((i, x)).collect();
+/// assert_eq!(results, vec![(0, 1), (1, 2)]);
+/// ```
+pub fn nth<'a, I, NV, CV, IV, D>(index: NV, collection: CV, item: IV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    NV: IntoVal<usize>,
+    CV: IntoVal<LVec<I>>,
+    IV: IntoVal<I>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    Goal::constraint(Nth {
+        index: index.into_val(),
+        collection: collection.into_val(),
+        item: item.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that relates an item to its (zero based)
+/// index in an `LVec<T>`.
+///
+/// This is the same relation as [`nth`] with the arguments in lookup order.
+/// If the item appears more than once, the state is forked for each index.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let i = var();
+/// let goal: Goal<Collections> = lvec::index_of(1, lvec![1, 2, 1], i);
+/// let results: Vec<_> = goal.query(i).collect();
+/// assert_eq!(results, vec![0, 2]);
+/// ```
+pub fn index_of<'a, I, IV, CV, NV, D>(item: IV, collection: CV, index: NV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    IV: IntoVal<I>,
+    CV: IntoVal<LVec<I>>,
+    NV: IntoVal<usize>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    nth(index, collection, item)
+}
+
+/// Create a [`Goal`](canrun::goal) that relates an `LVec<T>` to a copy with
+/// the item at a (zero based) index replaced.
+///
+/// The goal waits for the index and either of the `LVec`s to be resolved. When
+/// only the new `LVec` is known, the item is taken from it and the original is
+/// unified with a copy that has an unbound value at that index.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::set_nth(lvec![1, 2, 3], 1, 5, x);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![vec![1, 5, 3]]);
+/// ```
+pub fn set_nth<'a, I, CV, NV, IV, RV, D>(
+    collection: CV,
+    index: NV,
+    item: IV,
+    updated: RV,
+) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + 'a,
+    CV: IntoVal<LVec<I>>,
+    NV: IntoVal<usize>,
+    IV: IntoVal<I>,
+    RV: IntoVal<LVec<I>>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    Goal::constraint(SetNth {
+        collection: collection.into_val(),
+        index: index.into_val(),
+        item: item.into_val(),
+        updated: updated.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Nth<I: Debug> {
+    index: Val<usize>,
+    collection: Val<LVec<I>>,
+    item: Val<I>,
+}
+
+impl<'a, I, D> Constraint<'a, D> for Nth<I>
+where
+    I: UnifyIn<'a, D> + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let collection = resolve_1(&self.collection, state)?;
+        let item = self.item.clone();
+        match state.resolve_val(&self.index) {
+            Val::Resolved(index) => {
+                let found = collection.vec.get(**index).cloned();
+                Ok(Box::new(move |state| state.unify(&found?, &item)))
+            }
+            Val::Var(_) => {
+                let goals: Vec<Goal<D>> = collection
+                    .vec
+                    .iter()
+                    .enumerate()
+                    .map(|(i, found)| {
+                        all![
+                            unify(self.index.clone(), i),
+                            unify::<I, _, _, D>(item.clone(), found.clone())
+                        ]
+                    })
+                    .collect();
+                Ok(Box::new(|state| Goal::any(goals).apply(state)))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SetNth<I: Debug> {
+    collection: Val<LVec<I>>,
+    index: Val<usize>,
+    item: Val<I>,
+    updated: Val<LVec<I>>,
+}
+
+impl<'a, I, D> Constraint<'a, D> for SetNth<I>
+where
+    I: UnifyIn<'a, D> + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>> + DomainType<'a, usize>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let index = *resolve_1(&self.index, state)?;
+        let item = self.item.clone();
+        match OneOfTwo::resolve(&self.collection, &self.updated, state)? {
+            OneOfTwo::A(collection, updated) => {
+                if index >= collection.len() {
+                    return Ok(Box::new(|_| None));
+                }
+                let mut replaced = collection.vec.clone();
+                replaced[index] = item;
+                let replaced = LVec { vec: replaced };
+                Ok(Box::new(move |state| {
+                    state.unify(&replaced.into_val(), &updated)
+                }))
+            }
+            OneOfTwo::B(collection, updated) => {
+                if index >= updated.len() {
+                    return Ok(Box::new(|_| None));
+                }
+                let mut original = updated.vec.clone();
+                let found = std::mem::replace(&mut original[index], var().into_val());
+                let original = LVec { vec: original };
+                Ok(Box::new(move |state| {
+                    state
+                        .unify(&found, &item)?
+                        .unify(&original.into_val(), &collection)
+                }))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lvec;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn nth_known_index() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::nth(2, lvec![1, 2, 3], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![3]);
+    }
+
+    #[test]
+    fn nth_out_of_bounds() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::nth(3, lvec![1, 2, 3], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn nth_waits_for_collection() {
+        let (x, xs) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![lvec::nth(0, xs, x), unify(xs, lvec![4, 5])];
+        util::assert_permutations_resolve_to(goals, x, vec![4]);
+    }
+
+    #[test]
+    fn index_of_finds_positions() {
+        let i = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::index_of(3, lvec![3, 2, 3], i)];
+        util::assert_permutations_resolve_to(goals, i, vec![0, 2]);
+    }
+
+    #[test]
+    fn set_nth_forwards() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::set_nth(lvec![1, 2], 0, 3, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![3, 2]]);
+    }
+
+    #[test]
+    fn set_nth_backwards() {
+        let (x, item) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lvec::set_nth(x, 1, item, lvec![1, 5, 3]),
+            unify(x, lvec![1, 2, 3]),
+        ];
+        util::assert_permutations_resolve_to(goals, item, vec![5]);
+    }
+
+    #[test]
+    fn set_nth_out_of_bounds() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::set_nth(lvec![1, 2], 2, 3, x)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,547 @@
+use crate::lvec::LVec;
+use canrun::goal::Goal;
+use canrun::state::{
+    constraints::{resolve_1, Constraint, OneOfTwo, ResolveFn, VarWatch},
+    Fork, State, StateIter,
+};
+use canrun::value::{IntoVal, LVar, Val};
+use canrun::{DomainType, UnifyIn};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Create a [`Goal`](canrun::goal) that succeeds if the items of an `LVec<T>`
+/// appear in another `LVec<T>` in the same order, though not necessarily next
+/// to each other.
+///
+/// Unlike [`subset`](crate::lvec::subset()), which matches a contiguous slice,
+/// this allows gaps. The goal waits for the collection to be resolved and then
+/// lazily forks the state for each possible match. If the subsequence itself
+/// is not resolved, every subsequence of the collection is generated. Repeated
+/// values in the collection are only tried once at each position, so they do
+/// not lead to repeated results.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::subsequence(lvec![1, x], lvec![1, 2, 3]);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![2, 3]);
+/// ```
+pub fn subsequence<'a, I, SV, CV, D>(subsequence: SV, collection: CV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    SV: IntoVal<LVec<I>>,
+    CV: IntoVal<LVec<I>>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    Goal::constraint(Subsequence {
+        subsequence: subsequence.into_val(),
+        collection: collection.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that succeeds if every item of an
+/// `LVec<T>` can be matched with a different item in another `LVec<T>`,
+/// regardless of order.
+///
+/// The goal waits for the collection to be resolved, then lazily forks the
+/// state for each way of assigning items in the sub-multiset to items in the
+/// collection. If the sub-multiset is not resolved, every selection of items
+/// is generated in every order. Repeated values in the collection are only
+/// tried once, so they do not lead to repeated results.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::sub_multiset(lvec![3, x], lvec![1, 2, 3]);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![1, 2]);
+/// ```
+pub fn sub_multiset<'a, I, SV, CV, D>(sub_multiset: SV, collection: CV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    SV: IntoVal<LVec<I>>,
+    CV: IntoVal<LVec<I>>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    Goal::constraint(SubMultiset {
+        sub_multiset: sub_multiset.into_val(),
+        collection: collection.into_val(),
+    })
+}
+
+/// Create a [`Goal`](canrun::goal) that succeeds if two `LVec<T>`s contain the
+/// same items in any order.
+///
+/// Once either `LVec` is resolved, the state is lazily forked for each
+/// distinct ordering of its items. When both are resolved down to their
+/// items, they are compared directly instead.
+///
+/// # Example:
+/// ```
+/// use canrun::{Goal, var};
+/// use canrun_collections::{lvec, example::Collections};
+///
+/// let x = var();
+/// let goal: Goal<Collections> = lvec::permutation(x, lvec![1, 2, 3]);
+/// let results: Vec<_> = goal.query(x).collect();
+/// assert_eq!(results, vec![
+///     vec![1, 2, 3],
+///     vec![1, 3, 2],
+///     vec![2, 1, 3],
+///     vec![2, 3, 1],
+///     vec![3, 1, 2],
+///     vec![3, 2, 1],
+/// ]);
+/// ```
+pub fn permutation<'a, I, AV, BV, D>(a: AV, b: BV) -> Goal<'a, D>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    AV: IntoVal<LVec<I>>,
+    BV: IntoVal<LVec<I>>,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    Goal::constraint(Permutation {
+        a: a.into_val(),
+        b: b.into_val(),
+    })
+}
+
+#[derive(Debug)]
+struct Subsequence<I: Debug> {
+    subsequence: Val<LVec<I>>,
+    collection: Val<LVec<I>>,
+}
+
+impl<'a, I, D> Constraint<'a, D> for Subsequence<I>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let collection = resolve_1(&self.collection, state)?;
+        let fork = Rc::new(Choose {
+            target: self.subsequence.clone(),
+            collection,
+        });
+        Ok(Box::new(|state| state.fork(fork)))
+    }
+}
+
+/// Match the items of `target` with items of `collection` in the same order,
+/// skipping any number of collection items in between.
+#[derive(Debug)]
+struct Choose<I: Debug> {
+    target: Val<LVec<I>>,
+    collection: Rc<LVec<I>>,
+}
+
+impl<'a, I, D> Fork<'a, D> for Choose<I>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    fn fork(&self, state: State<'a, D>) -> StateIter<'a, D> {
+        let collection = self.collection.clone();
+        match state.resolve_val(&self.target).clone() {
+            Val::Resolved(target) => choose(state, target, collection, 0, 0),
+            Val::Var(var) => {
+                // Generate a target of fresh vars for each possible length
+                Box::new((0..=collection.len()).flat_map(move |length| {
+                    let fresh: Vec<Val<I>> = (0..length).map(|_| Val::Var(LVar::new())).collect();
+                    let target = Rc::new(LVec { vec: fresh });
+                    let collection = collection.clone();
+                    state
+                        .clone()
+                        .unify(&Val::Var(var), &Val::Resolved(target.clone()))
+                        .map(|state| choose(state, target, collection, 0, 0))
+                        .into_iter()
+                        .flatten()
+                }))
+            }
+        }
+    }
+}
+
+/// Match item `matched` of `target` with each collection item from `start`
+/// on, leaving enough items for the rest of `target`, and recurse into the
+/// items after it. Equal collection items would lead to the same states, so
+/// only the first of them is tried.
+fn choose<'a, I, D>(
+    state: State<'a, D>,
+    target: Rc<LVec<I>>,
+    collection: Rc<LVec<I>>,
+    matched: usize,
+    start: usize,
+) -> StateIter<'a, D>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    D: DomainType<'a, I>,
+{
+    let item = match target.vec.get(matched) {
+        Some(item) => item.clone(),
+        None => return Box::new(std::iter::once(state)),
+    };
+    let remaining = target.len() - matched;
+    if collection.len() < start + remaining {
+        return Box::new(std::iter::empty());
+    }
+    let mut candidates: Vec<usize> = Vec::new();
+    for index in start..=collection.len() - remaining {
+        let value = state.resolve_val(&collection.vec[index]);
+        let tried = candidates
+            .iter()
+            .any(|&other| state.resolve_val(&collection.vec[other]) == value);
+        if !tried {
+            candidates.push(index);
+        }
+    }
+    Box::new(candidates.into_iter().flat_map(move |index| {
+        let (target, collection) = (target.clone(), collection.clone());
+        state
+            .clone()
+            .unify(&item, &collection.vec[index])
+            .map(|state| choose(state, target, collection, matched + 1, index + 1))
+            .into_iter()
+            .flatten()
+    }))
+}
+
+#[derive(Debug)]
+struct SubMultiset<I: Debug> {
+    sub_multiset: Val<LVec<I>>,
+    collection: Val<LVec<I>>,
+}
+
+impl<'a, I, D> Constraint<'a, D> for SubMultiset<I>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let collection = resolve_1(&self.collection, state)?;
+        let fork = Rc::new(Arrange {
+            target: self.sub_multiset.clone(),
+            collection,
+            exact: false,
+        });
+        Ok(Box::new(|state| state.fork(fork)))
+    }
+}
+
+#[derive(Debug)]
+struct Permutation<I: Debug> {
+    a: Val<LVec<I>>,
+    b: Val<LVec<I>>,
+}
+
+impl<'a, I, D> Constraint<'a, D> for Permutation<I>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    fn attempt(&self, state: &State<'a, D>) -> Result<ResolveFn<'a, D>, VarWatch> {
+        let (target, collection) = match OneOfTwo::resolve(&self.a, &self.b, state)? {
+            OneOfTwo::A(a, b) => (b, a),
+            OneOfTwo::B(a, b) => (a, b),
+        };
+        let fork = Rc::new(Arrange {
+            target,
+            collection,
+            exact: true,
+        });
+        Ok(Box::new(|state| state.fork(fork)))
+    }
+}
+
+/// Match the items of `target` with distinct items of `collection`, in any
+/// order. When `exact`, every item of the collection must be used.
+#[derive(Debug)]
+struct Arrange<I: Debug> {
+    target: Val<LVec<I>>,
+    collection: Rc<LVec<I>>,
+    exact: bool,
+}
+
+impl<'a, I, D> Fork<'a, D> for Arrange<I>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    LVec<I>: UnifyIn<'a, D>,
+    D: DomainType<'a, I> + DomainType<'a, LVec<I>>,
+{
+    fn fork(&self, state: State<'a, D>) -> StateIter<'a, D> {
+        let collection = self.collection.clone();
+        let n = collection.len();
+        let unused: Vec<usize> = (0..n).collect();
+        match state.resolve_val(&self.target).clone() {
+            Val::Resolved(target) => {
+                if target.len() > n || self.exact && target.len() < n {
+                    Box::new(std::iter::empty())
+                } else if matches_directly(&state, &target, &collection) {
+                    Box::new(std::iter::once(state))
+                } else {
+                    arrange(state, target, collection, unused)
+                }
+            }
+            Val::Var(var) => {
+                // Generate a target of fresh vars for each possible length
+                let lengths = if self.exact { n..=n } else { 0..=n };
+                Box::new(lengths.flat_map(move |length| {
+                    let fresh: Vec<Val<I>> = (0..length).map(|_| Val::Var(LVar::new())).collect();
+                    let target = Rc::new(LVec { vec: fresh });
+                    let collection = collection.clone();
+                    let unused = unused.clone();
+                    state
+                        .clone()
+                        .unify(&Val::Var(var), &Val::Resolved(target.clone()))
+                        .map(|state| arrange(state, target, collection, unused))
+                        .into_iter()
+                        .flatten()
+                }))
+            }
+        }
+    }
+}
+
+/// Match the next item of `target` with each of the `unused` collection
+/// items, recursing into the rest. Equal collection items lead to the same
+/// states, so only the first of them is tried.
+fn arrange<'a, I, D>(
+    state: State<'a, D>,
+    target: Rc<LVec<I>>,
+    collection: Rc<LVec<I>>,
+    unused: Vec<usize>,
+) -> StateIter<'a, D>
+where
+    I: UnifyIn<'a, D> + PartialEq + 'a,
+    D: DomainType<'a, I>,
+{
+    let item = match target.vec.get(collection.len() - unused.len()) {
+        Some(item) => item.clone(),
+        None => return Box::new(std::iter::once(state)),
+    };
+    let mut candidates: Vec<usize> = Vec::new();
+    for &index in &unused {
+        let value = state.resolve_val(&collection.vec[index]);
+        let tried = candidates
+            .iter()
+            .any(|&other| state.resolve_val(&collection.vec[other]) == value);
+        if !tried {
+            candidates.push(index);
+        }
+    }
+    Box::new(candidates.into_iter().flat_map(move |index| {
+        let rest: Vec<usize> = unused.iter().copied().filter(|i| *i != index).collect();
+        let (target, collection) = (target.clone(), collection.clone());
+        state
+            .clone()
+            .unify(&item, &collection.vec[index])
+            .map(|state| arrange(state, target, collection, rest))
+            .into_iter()
+            .flatten()
+    }))
+}
+
+/// Check whether every item of `target` has its own equal item in
+/// `collection`, when all of them are already resolved.
+fn matches_directly<'a, I, D>(state: &State<'a, D>, target: &LVec<I>, collection: &LVec<I>) -> bool
+where
+    I: PartialEq + Debug,
+    D: DomainType<'a, I>,
+{
+    let resolve = |vec: &LVec<I>| -> Option<Vec<Rc<I>>> {
+        vec.vec
+            .iter()
+            .map(|item| match state.resolve_val(item) {
+                Val::Resolved(value) => Some(value.clone()),
+                Val::Var(_) => None,
+            })
+            .collect()
+    };
+    let (target, mut remaining) = match (resolve(target), resolve(collection)) {
+        (Some(target), Some(collection)) => (target, collection),
+        _ => return false,
+    };
+    target.iter().all(
+        |item| match remaining.iter().position(|other| other == item) {
+            Some(index) => {
+                remaining.swap_remove(index);
+                true
+            }
+            None => false,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::example::Collections;
+    use crate::lvec;
+    use crate::lvec::LVec;
+    use canrun::goal::{unify, Goal};
+    use canrun::util;
+    use canrun::value::var;
+
+    #[test]
+    fn subsequence_allows_gaps() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, lvec![1, 3]), lvec::subsequence(x, lvec![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 3]]);
+    }
+
+    #[test]
+    fn subsequence_keeps_order() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, lvec![3, 1]), lvec::subsequence(x, lvec![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn subsequence_generates_all() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::subsequence(x, lvec![1, 2])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![], vec![1], vec![2], vec![1, 2]]);
+    }
+
+    #[test]
+    fn subsequence_skips_repeated_items() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::subsequence(x, lvec![1, 1, 1])];
+        util::assert_permutations_resolve_to(
+            goals,
+            x,
+            vec![vec![], vec![1], vec![1, 1], vec![1, 1, 1]],
+        );
+
+        let goals: Vec<Goal<Collections>> = vec![lvec::subsequence(x, lvec![1, 2, 1])];
+        util::assert_permutations_resolve_to(
+            goals,
+            x,
+            vec![
+                vec![],
+                vec![1],
+                vec![2],
+                vec![1, 2],
+                vec![1, 1],
+                vec![2, 1],
+                vec![1, 2, 1],
+            ],
+        );
+    }
+
+    #[test]
+    fn subsequence_of_resolved_matches_once() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![
+            lvec::subsequence(lvec![1, x], lvec![1, 1, 2, 1]),
+            unify(x, 1),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![1]);
+    }
+
+    #[test]
+    fn subsequence_is_lazy() {
+        let x = var();
+        let goal: Goal<Collections> = lvec::subsequence(x, LVec::from(0..40));
+        let first: Vec<Vec<i32>> = goal.query(x).take(3).collect();
+        assert_eq!(first, vec![vec![], vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn sub_multiset_ignores_order() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, lvec![3, 1]), lvec::sub_multiset(x, lvec![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![3, 1]]);
+    }
+
+    #[test]
+    fn sub_multiset_counts_repeats() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, lvec![1, 1]), lvec::sub_multiset(x, lvec![1, 2])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn permutation_of_resolved() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<Collections>> = vec![
+            lvec::permutation(lvec![x, 2, y], lvec![3, 2, 1]),
+            unify(x, 1),
+        ];
+        util::assert_permutations_resolve_to(goals, y, vec![3]);
+    }
+
+    #[test]
+    fn permutation_length_mismatch() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> =
+            vec![unify(x, lvec![1, 2]), lvec::permutation(x, lvec![1, 2, 3])];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn permutation_skips_repeated_items() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::permutation(lvec![1, 1, 1], x)];
+        util::assert_permutations_resolve_to(goals, x, vec![vec![1, 1, 1]]);
+    }
+
+    #[test]
+    fn permutation_of_resolved_is_direct() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![
+            lvec::permutation(
+                lvec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+                lvec![12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1],
+            ),
+            unify(x, 1),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![1]);
+        let goals: Vec<Goal<Collections>> = vec![
+            lvec::permutation(
+                lvec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+                lvec![12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 2],
+            ),
+            unify(x, 1),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn sub_multiset_generates_distinct() {
+        let x = var();
+        let goals: Vec<Goal<Collections>> = vec![lvec::sub_multiset(x, lvec![1, 2, 1])];
+        util::assert_permutations_resolve_to(
+            goals,
+            x,
+            vec![
+                vec![],
+                vec![1],
+                vec![2],
+                vec![1, 2],
+                vec![1, 1],
+                vec![2, 1],
+                vec![1, 2, 1],
+                vec![1, 1, 2],
+                vec![2, 1, 1],
+            ],
+        );
+    }
+}
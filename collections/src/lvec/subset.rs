@@ -9,10 +9,11 @@ use canrun::{DomainType, UnifyIn};
 use std::fmt::Debug;
 use std::iter::repeat;
 
-/// Create a [`Goal`](canrun::goal) that attempts to unify a `Val<T>` with any
-/// of the items in a `LVec<T>`.
+/// Create a [`Goal`](canrun::goal) that attempts to unify an `LVec<T>` with
+/// any contiguous slice of another `LVec<T>`.
 ///
-/// This goal will fork the state for each match found.
+/// This goal will fork the state for each match found. See
+/// [`subsequence`](crate::lvec::subsequence()) to allow gaps between items.
 /// # Examples:
 /// ```
 /// use canrun::{Goal, val, var, all, unify};
@@ -0,0 +1,264 @@
+//! Forks that pair up the items of two collections when their items may be
+//! unified in any order, shared by the set and map types.
+use canrun::state::{Fork, State, StateIter};
+use canrun::{Domain, DomainType, UnifyIn, Val};
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+/// An item from one collection that may be paired with an item from another.
+pub(crate) trait Item<'a, D: Domain<'a>>: Clone + Debug + 'a {
+    /// Unify the two items in the state.
+    fn unify_with(&self, other: &Self, state: State<'a, D>) -> Option<State<'a, D>>;
+
+    /// Whether the items are already identical, so that pairing with one is
+    /// the same as pairing with the other.
+    fn same_as(&self, other: &Self, state: &State<'a, D>) -> bool;
+
+    /// Whether the item is unbound, so that it could still merge with another
+    /// item in the same collection.
+    fn is_var(&self) -> bool;
+}
+
+impl<'a, T, D> Item<'a, D> for Val<T>
+where
+    T: UnifyIn<'a, D> + PartialEq + Debug + 'a,
+    D: DomainType<'a, T>,
+{
+    fn unify_with(&self, other: &Self, state: State<'a, D>) -> Option<State<'a, D>> {
+        state.unify(self, other)
+    }
+
+    fn same_as(&self, other: &Self, state: &State<'a, D>) -> bool {
+        state.resolve_val(self) == state.resolve_val(other)
+    }
+
+    fn is_var(&self) -> bool {
+        Val::is_var(self)
+    }
+}
+
+/// Map entries are paired on their keys and then their values.
+impl<'a, K, V, D> Item<'a, D> for (Val<K>, Val<V>)
+where
+    K: UnifyIn<'a, D> + PartialEq + Debug + 'a,
+    V: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, K> + DomainType<'a, V>,
+{
+    fn unify_with(&self, other: &Self, state: State<'a, D>) -> Option<State<'a, D>> {
+        state.unify(&self.0, &other.0)?.unify(&self.1, &other.1)
+    }
+
+    fn same_as(&self, other: &Self, state: &State<'a, D>) -> bool {
+        // Keys are unique within a map, so the same key means the same entry.
+        state.resolve_val(&self.0) == state.resolve_val(&other.0)
+    }
+
+    fn is_var(&self) -> bool {
+        self.0.is_var()
+    }
+}
+
+/// Unify two closed collections, given the items of each that did not have
+/// an identical match in the other.
+///
+/// Equal numbers of unmatched items are paired one-to-one. Otherwise the
+/// sides can only even out if some unbound items end up merging, so each
+/// unmatched item may pair with any item on the other side.
+pub(crate) fn unify_unmatched<'a, D, E>(
+    state: State<'a, D>,
+    (a_unmatched, a_items): (Vec<E>, Vec<E>),
+    (b_unmatched, b_items): (Vec<E>, Vec<E>),
+) -> Option<State<'a, D>>
+where
+    D: Domain<'a> + 'a,
+    E: Item<'a, D>,
+{
+    if a_unmatched.is_empty() && b_unmatched.is_empty() {
+        Some(state)
+    } else if a_unmatched.len() == b_unmatched.len() {
+        state.fork(Rc::new(Matching {
+            a: a_unmatched,
+            b: b_unmatched,
+            leftover: None,
+        }))
+    } else if a_unmatched.iter().chain(&b_unmatched).any(E::is_var) {
+        state
+            .fork(Rc::new(Cover {
+                from: a_unmatched,
+                to: b_items,
+                leftover: None,
+            }))?
+            .fork(Rc::new(Cover {
+                from: b_unmatched,
+                to: a_items,
+                leftover: None,
+            }))
+    } else {
+        None
+    }
+}
+
+/// Called with the items of `b` that were not paired once every item of `a`
+/// has been.
+pub(crate) type Leftover<'a, D, E> = Rc<dyn Fn(State<'a, D>, Vec<E>) -> Option<State<'a, D>> + 'a>;
+
+/// Lazily pairs every item of `a` with a different item of `b`.
+///
+/// Pairs that cannot unify on their own are ruled out before searching, and
+/// items of `b` that are already identical are only tried once, so every
+/// matching is yielded once. Without a `leftover` function, `a` and `b` must
+/// be the same length.
+pub(crate) struct Matching<'a, D: Domain<'a>, E> {
+    pub(crate) a: Vec<E>,
+    pub(crate) b: Vec<E>,
+    pub(crate) leftover: Option<Leftover<'a, D, E>>,
+}
+
+impl<'a, D: Domain<'a>, E: Debug> Debug for Matching<'a, D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Matching {:?} {:?}", self.a, self.b)
+    }
+}
+
+struct Search<'a, D: Domain<'a>, E> {
+    a: Vec<E>,
+    b: Vec<E>,
+    candidates: Vec<Vec<usize>>,
+    leftover: Option<Leftover<'a, D, E>>,
+}
+
+impl<'a, D: Domain<'a> + 'a, E: Item<'a, D>> Search<'a, D, E> {
+    /// Pair the `i`th item of `a` with each of the `unused` items of `b` it
+    /// could unify with, carrying the state from the pairs chosen so far.
+    fn step(self: Rc<Self>, state: State<'a, D>, i: usize, unused: Vec<usize>) -> StateIter<'a, D> {
+        let item = match self.a.get(i) {
+            Some(item) => item.clone(),
+            None => {
+                let finished = match &self.leftover {
+                    Some(leftover) => {
+                        leftover(state, unused.iter().map(|j| self.b[*j].clone()).collect())
+                    }
+                    None => Some(state),
+                };
+                return Box::new(finished.into_iter());
+            }
+        };
+        let mut tried: Vec<usize> = Vec::new();
+        for &j in &self.candidates[i] {
+            let repeat = tried
+                .iter()
+                .any(|&other| self.b[other].same_as(&self.b[j], &state));
+            if unused.contains(&j) && !repeat {
+                tried.push(j);
+            }
+        }
+        Box::new(tried.into_iter().flat_map(move |j| {
+            let rest = unused.iter().copied().filter(|other| *other != j).collect();
+            let search = self.clone();
+            item.unify_with(&self.b[j], state.clone())
+                .map(|state| search.step(state, i + 1, rest))
+                .into_iter()
+                .flatten()
+        }))
+    }
+}
+
+impl<'a, D: Domain<'a> + 'a, E: Item<'a, D>> Fork<'a, D> for Matching<'a, D, E> {
+    fn fork(&self, state: State<'a, D>) -> StateIter<'a, D> {
+        let candidates: Vec<Vec<usize>> = self
+            .a
+            .iter()
+            .map(|a| {
+                (0..self.b.len())
+                    .filter(|&j| a.unify_with(&self.b[j], state.clone()).is_some())
+                    .collect()
+            })
+            .collect();
+        if candidates.iter().any(Vec::is_empty) {
+            return Box::new(std::iter::empty());
+        }
+        let search = Rc::new(Search {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            candidates,
+            leftover: self.leftover.clone(),
+        });
+        search.step(state, 0, (0..self.b.len()).collect())
+    }
+}
+
+/// Lazily pairs every item of `from` with some item of `to`, not necessarily
+/// a different one.
+///
+/// This is used when unbound items may end up merging, so the collections do
+/// not need to be the same size. An item that is already identical to one in
+/// `to` is only paired with that one, and identical items of `to` are only
+/// tried once. A `leftover` function is called with the items of `to` that
+/// ended up identical to none of the items of `from`.
+pub(crate) struct Cover<'a, D: Domain<'a>, E> {
+    pub(crate) from: Vec<E>,
+    pub(crate) to: Vec<E>,
+    pub(crate) leftover: Option<Leftover<'a, D, E>>,
+}
+
+impl<'a, D: Domain<'a>, E: Debug> Debug for Cover<'a, D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cover {:?} {:?}", self.from, self.to)
+    }
+}
+
+impl<'a, D: Domain<'a> + 'a, E: Item<'a, D>> Cover<'a, D, E> {
+    fn step(self: Rc<Self>, state: State<'a, D>, i: usize) -> StateIter<'a, D> {
+        let item = match self.from.get(i) {
+            Some(item) => item.clone(),
+            None => {
+                let finished = match &self.leftover {
+                    Some(leftover) => {
+                        let unused = self
+                            .to
+                            .iter()
+                            .filter(|to| !self.from.iter().any(|from| from.same_as(to, &state)))
+                            .cloned()
+                            .collect();
+                        leftover(state, unused)
+                    }
+                    None => Some(state),
+                };
+                return Box::new(finished.into_iter());
+            }
+        };
+        if let Some(other) = self.to.iter().find(|other| item.same_as(other, &state)) {
+            return match item.unify_with(other, state) {
+                Some(state) => self.step(state, i + 1),
+                None => Box::new(std::iter::empty()),
+            };
+        }
+        let mut tried: Vec<usize> = Vec::new();
+        for j in 0..self.to.len() {
+            if !tried
+                .iter()
+                .any(|&k| self.to[k].same_as(&self.to[j], &state))
+            {
+                tried.push(j);
+            }
+        }
+        Box::new(tried.into_iter().flat_map(move |j| {
+            let cover = self.clone();
+            item.unify_with(&self.to[j], state.clone())
+                .map(|state| cover.step(state, i + 1))
+                .into_iter()
+                .flatten()
+        }))
+    }
+}
+
+impl<'a, D: Domain<'a> + 'a, E: Item<'a, D>> Fork<'a, D> for Cover<'a, D, E> {
+    fn fork(&self, state: State<'a, D>) -> StateIter<'a, D> {
+        let cover = Rc::new(Cover {
+            from: self.from.clone(),
+            to: self.to.clone(),
+            leftover: self.leftover.clone(),
+        });
+        cover.step(state, 0)
+    }
+}
@@ -1,9 +1,13 @@
 //! Constrain the set of types that you can reason about in a particular
 //! context.
 
+mod any;
 pub mod example;
 
+pub use any::{AnyDomain, AnyValue};
+
 use crate::state::State;
+use crate::unify::UnifyIn;
 use crate::value::{LVar, Val};
 use im_rc::HashMap;
 use std::fmt::Debug;
@@ -78,6 +82,12 @@ impl<'a, T: Debug> Clone for DomainValues<T> {
     }
 }
 
+/// Unifies two values of a type within a generic domain.
+///
+/// Created by the `domain!` macro and intended for internal use.
+#[doc(hidden)]
+pub type UnifyFn<'a, D, T> = fn(State<'a, D>, Val<T>, Val<T>) -> Option<State<'a, D>>;
+
 /// Allows a [`State`](crate::state) to retrieve values of a specific type from
 /// a [domain](crate::domains).
 ///
@@ -95,7 +105,7 @@ pub trait DomainType<'a, T: Debug>: Domain<'a> {
     {
         match val {
             Val::Var(var) => {
-                let resolved = self.values_as_ref().0.get(var);
+                let resolved = self.values_as_ref().and_then(|values| values.0.get(var));
                 match resolved {
                     Some(Val::Var(found)) if found == var => val,
                     Some(found) => self.resolve(found),
@@ -112,11 +122,13 @@ pub trait DomainType<'a, T: Debug>: Domain<'a> {
     }
 
     #[doc(hidden)]
-    fn values_as_ref(&self) -> &DomainValues<T>;
+    fn values_as_ref(&self) -> Option<&DomainValues<T>>;
     #[doc(hidden)]
     fn values_as_mut(&mut self) -> &mut DomainValues<T>;
     #[doc(hidden)]
-    fn into_domain_val(val: Val<T>) -> Self::Value;
+    fn into_domain_val(val: Val<T>) -> Self::Value
+    where
+        T: UnifyIn<'a, Self>;
 }
 
 /// Generate [Domain] structs and other associated types and impls.
@@ -163,6 +175,55 @@ pub trait DomainType<'a, T: Debug>: Domain<'a> {
 /// # fn main() {}
 /// ```
 ///
+/// Domains can be generic, with optional bounds and a where clause. This
+/// allows a library to provide goals that work with whatever element type the
+/// user supplies.
+/// ```
+/// use canrun::Val;
+/// canrun::domain! {
+///     pub Pairs<T> where T: PartialEq {
+///         T,
+///         (Val<T>, Val<T>),
+///     }
+/// }
+/// # fn main() {
+/// # use canrun::{unify, var, ltup, Goal};
+/// let x = var();
+/// let goal: Goal<Pairs<i32>> = unify(ltup!(x, 2), ltup!(1, 2));
+/// assert_eq!(goal.query(x).collect::<Vec<_>>(), vec![1]);
+/// # }
+/// ```
+///
+/// Since each type gets its own impl, the types must not be able to overlap
+/// for any choice of parameters. A domain with both `T` and `i32` would
+/// conflict when `T = i32`. The `'a` lifetime is reserved for the generated
+/// impls.
+///
+/// A domain can extend another domain by naming it after a colon. The base
+/// domain's types are forwarded to it with a blanket
+/// [`DomainType`](crate::domains::DomainType) impl, so goals that are generic
+/// over `D: DomainType<'a, T>` keep working in the extended domain. Extensions
+/// can be chained.
+/// ```
+/// canrun::domain! {
+///     pub Base { i32 }
+/// }
+/// canrun::domain! {
+///     pub Extended: Base { String }
+/// }
+/// # fn main() {
+/// # use canrun::{unify, var, Goal};
+/// let (x, y) = (var(), var());
+/// let goal: Goal<Extended> = canrun::all![unify(x, 1), unify(y, String::from("one"))];
+/// assert_eq!(goal.query((x, y)).collect::<Vec<_>>(), vec![(1, String::from("one"))]);
+/// # }
+/// ```
+///
+/// Types inherited from the base domain must be `'static`, and their values
+/// are type erased so they are a bit slower to unify. The extra types must not
+/// overlap with the base domain. If the base domain is defined in another
+/// crate, the extra types generally need to be local to the extending crate.
+///
 /// Any types you add to a domain must implement the
 /// [`UnifyIn`](crate::unify::UnifyIn) trait. Canrun includes default
 /// implementations for almost all primitive types and collection types are
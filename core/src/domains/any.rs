@@ -0,0 +1,211 @@
+use super::{Domain, DomainType, DomainValues};
+use crate::state::State;
+use crate::unify::UnifyIn;
+use crate::value::Val;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// A [`Domain`] that accepts any `'static` type at runtime.
+///
+/// The [`domain!`](super::domain) macro requires every type to be listed up
+/// front, which makes shared domains awkward to grow and can lead to confusing
+/// trait errors when a nested type (such as `LVec<LHouse>`) is forgotten.
+/// `AnyDomain` instead stores values keyed by their [`TypeId`], so any type
+/// that implements [`UnifyIn`] can be used without declaring it.
+///
+/// The tradeoff is an extra layer of dynamic dispatch and downcasting for every
+/// binding, so the macro generated domains remain the faster option.
+///
+/// # Example:
+/// ```
+/// use canrun::{all, unify, var, ltup, Goal};
+/// use canrun::domains::AnyDomain;
+///
+/// let (x, y) = (var(), var());
+/// let goal: Goal<AnyDomain> = all![
+///     unify(x, ltup!(1, "two")),
+///     unify(x, ltup!(y, "two")),
+/// ];
+/// let results: Vec<i32> = goal.query(y).collect();
+/// assert_eq!(results, vec![1]);
+/// ```
+#[derive(Default)]
+pub struct AnyDomain {
+    values: HashMap<TypeId, Box<dyn AnyValues>>,
+}
+
+impl Clone for AnyDomain {
+    fn clone(&self) -> Self {
+        AnyDomain {
+            values: self
+                .values
+                .iter()
+                .map(|(id, values)| (*id, values.clone_box()))
+                .collect(),
+        }
+    }
+}
+
+impl Debug for AnyDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.values.values()).finish()
+    }
+}
+
+impl<'a> Domain<'a> for AnyDomain {
+    type Value = AnyValue<'a, AnyDomain>;
+
+    fn new() -> Self {
+        AnyDomain::default()
+    }
+
+    fn unify_domain_values(
+        state: State<'a, Self>,
+        a: Self::Value,
+        b: Self::Value,
+    ) -> Option<State<'a, Self>> {
+        a.unify(state, &b)
+    }
+}
+
+impl<'a, T: Debug + 'static> DomainType<'a, T> for AnyDomain {
+    fn values_as_ref(&self) -> Option<&DomainValues<T>> {
+        let values = self.values.get(&TypeId::of::<T>())?;
+        values.as_any().downcast_ref()
+    }
+
+    fn values_as_mut(&mut self) -> &mut DomainValues<T> {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(DomainValues::<T>::new()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("AnyDomain values are keyed by their TypeId")
+    }
+
+    fn into_domain_val(val: Val<T>) -> Self::Value
+    where
+        T: UnifyIn<'a, Self>,
+    {
+        AnyValue::new(val)
+    }
+}
+
+/// Type erased [`DomainValues`] so they can be stored side by side.
+trait AnyValues: Debug {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn clone_box(&self) -> Box<dyn AnyValues>;
+}
+
+impl<T: Debug + 'static> AnyValues for DomainValues<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn clone_box(&self) -> Box<dyn AnyValues> {
+        Box::new(self.clone())
+    }
+}
+
+/// A type erased [`Val`] that remembers how to unify itself with another
+/// value of the same type.
+///
+/// Used as the [`Domain::Value`] of domains that do not know their full set of
+/// types at compile time, such as [`AnyDomain`]. Typically for internal use.
+pub struct AnyValue<'a, D>(Rc<dyn UnifyAny<'a, D> + 'a>);
+
+impl<'a, D: Domain<'a>> AnyValue<'a, D> {
+    #[doc(hidden)]
+    pub fn new<T>(val: Val<T>) -> Self
+    where
+        T: UnifyIn<'a, D> + 'static,
+        D: DomainType<'a, T>,
+    {
+        AnyValue(Rc::new(Typed(val)))
+    }
+
+    #[doc(hidden)]
+    pub fn unify(&self, state: State<'a, D>, other: &Self) -> Option<State<'a, D>> {
+        self.0.unify_with(state, &*other.0)
+    }
+}
+
+impl<'a, D> Clone for AnyValue<'a, D> {
+    fn clone(&self) -> Self {
+        AnyValue(self.0.clone())
+    }
+}
+
+impl<'a, D> Debug for AnyValue<'a, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+trait UnifyAny<'a, D>: Debug {
+    fn as_any(&self) -> &dyn Any;
+    fn unify_with(&self, state: State<'a, D>, other: &dyn UnifyAny<'a, D>) -> Option<State<'a, D>>
+    where
+        D: Domain<'a>;
+}
+
+#[derive(Debug)]
+struct Typed<T: Debug>(Val<T>);
+
+impl<'a, T, D> UnifyAny<'a, D> for Typed<T>
+where
+    T: UnifyIn<'a, D> + 'static,
+    D: DomainType<'a, T>,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn unify_with(&self, state: State<'a, D>, other: &dyn UnifyAny<'a, D>) -> Option<State<'a, D>>
+    where
+        D: Domain<'a>,
+    {
+        // Values of different types can never be unified, though the typed
+        // goal constructors should prevent this from happening.
+        let other = other.as_any().downcast_ref::<Typed<T>>()?;
+        state.unify(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AnyDomain;
+    use crate as canrun;
+    use crate::{all, ltup, unify, util, val, var, Goal, Val};
+
+    #[test]
+    fn unifies_unlisted_types() {
+        let (x, y) = (var(), var());
+        let goals: Vec<Goal<AnyDomain>> = vec![
+            unify(x, ltup!(1, "two", String::from("three"))),
+            unify(x, ltup!(1, y, String::from("three"))),
+        ];
+        util::assert_permutations_resolve_to(goals, y, vec!["two"]);
+    }
+
+    #[test]
+    fn fails_with_different_values() {
+        let x = var();
+        let goals: Vec<Goal<AnyDomain>> = vec![unify(x, 1), unify(x, 2)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+
+    #[test]
+    fn keeps_types_separate() {
+        let (x, y): (Val<i32>, Val<u8>) = (val!(var()), val!(var()));
+        let goal: Goal<AnyDomain> = all![unify(x.clone(), 1), unify(y.clone(), 2)];
+        let results: Vec<_> = goal.query((x, y)).collect();
+        assert_eq!(results, vec![(1, 2)]);
+    }
+}
@@ -20,6 +20,7 @@ mod any;
 mod both;
 mod custom;
 mod either;
+mod fresh;
 mod lazy;
 pub mod project;
 mod unify;
@@ -35,10 +36,63 @@ pub use custom::custom;
 #[doc(inline)]
 pub use either::either;
 #[doc(inline)]
-pub use lazy::lazy;
+pub use fresh::fresh;
+#[doc(inline)]
+pub use lazy::{lazy, lazy_named};
 #[doc(inline)]
 pub use unify::unify;
 
+/// Declare named relation functions.
+///
+/// Each parameter is written with the type of value it relates, and accepts
+/// anything that implements [`IntoVal`](crate::value::IntoVal) for that type.
+/// The body is wrapped in a [lazy](crate::goal::lazy()) goal so relations can
+/// safely call themselves recursively. Parameters are available in the body as
+/// [`Val`s](crate::value::Val).
+///
+/// The relation's name is shown in the goal's debug output and logged at the
+/// `trace` level each time it runs (see [`lazy_named`]).
+///
+/// Without a return type, the relation is generic over any domain `D` that
+/// contains all of the parameter types. Additional bounds can be added in a
+/// where clause.
+///
+/// # Examples
+///
+/// ```
+/// use canrun::{all, assert_1, defrel, either, fresh, map_1, unify, var, Goal};
+/// use canrun::domains::example::I32;
+///
+/// defrel! {
+///     /// Relates `x` to every number from `n` down to zero.
+///     fn count_down(n: i32, x: i32) -> Goal<'a, I32> {
+///         either(
+///             unify(x.clone(), n.clone()),
+///             fresh!(|m| all![
+///                 map_1(n.clone(), m, |n| n - 1, |m| m + 1),
+///                 assert_1(m, |m| *m >= 0),
+///                 count_down(m, x.clone()),
+///             ]),
+///         )
+///     }
+///
+///     /// Generic over any domain containing `T`.
+///     fn same<T>(a: T, b: T) {
+///         unify(a, b)
+///     }
+/// }
+///
+/// let x = var();
+/// let goal: Goal<I32> = count_down(2, x);
+/// let result: Vec<_> = goal.query(x).collect();
+/// assert_eq!(result, vec![2, 1, 0]);
+///
+/// let goal: Goal<I32> = same(x, 1);
+/// let result: Vec<_> = goal.query(x).collect();
+/// assert_eq!(result, vec![1]);
+/// ```
+pub use canrun_codegen::defrel;
+
 #[derive(Clone, Debug)]
 pub(crate) enum GoalEnum<'a, D: Domain<'a>> {
     Succeed,
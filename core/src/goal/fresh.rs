@@ -0,0 +1,55 @@
+/// Create a [lazy](crate::goal::lazy()) [goal](crate::goal::Goal) that
+/// introduces new [logical vars](crate::value::LVar).
+///
+/// The vars are created each time the goal is evaluated, which is typically
+/// what you want when writing recursive relations. The variable types are
+/// usually inferred, but may be annotated with the type of value they hold.
+///
+/// Since the goal may be evaluated more than once, the body must be callable
+/// more than once. Any captured [`Val`](crate::value::Val) needs to be cloned
+/// before use (`LVar`s are `Copy`, so they can be used freely).
+///
+/// # Examples
+///
+/// ```
+/// use canrun::{Goal, both, fresh, unify, var};
+/// use canrun::domains::example::I32;
+///
+/// let x = var();
+/// let goal: Goal<I32> = fresh!(|y, z: i32| both(unify(y, z), unify(x, y)));
+/// let goal: Goal<I32> = canrun::all![goal, unify(x, 1)];
+/// let result: Vec<_> = goal.query(x).collect();
+/// assert_eq!(result, vec![1])
+/// ```
+#[macro_export]
+macro_rules! fresh {
+    (|$($var:ident $(: $t:ty)?),* $(,)?| $body:expr) => {
+        $crate::goal::lazy(move || {
+            $(let $var $(: $crate::value::LVar<$t>)? = $crate::value::var();)*
+            $body
+        })
+    };
+}
+pub use fresh;
+
+#[cfg(test)]
+mod tests {
+    use crate::domains::example::I32;
+    use crate::goal::{both, either, unify, Goal};
+    use crate::util;
+    use crate::value::var;
+
+    #[test]
+    fn introduces_vars() {
+        let x = var();
+        let goal: Goal<I32> = fresh!(|y| both(unify(y, 1), unify(x, y)));
+        assert_eq!(util::goal_resolves_to(goal, x), vec![1]);
+    }
+
+    #[test]
+    fn annotated_types() {
+        let x = var();
+        let pick: Goal<I32> = fresh!(|y: i32| both(either(unify(y, 1), unify(y, 2)), unify(x, y)));
+        assert_eq!(util::goal_resolves_to(pick, x), vec![1, 2]);
+    }
+}
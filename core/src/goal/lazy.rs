@@ -5,15 +5,20 @@ use std::fmt;
 use std::rc::Rc;
 
 #[derive(Clone)]
-pub struct Lazy<'a, D: Domain<'a>>(Rc<dyn Fn() -> Goal<'a, D> + 'a>);
+pub struct Lazy<'a, D: Domain<'a>> {
+    func: Rc<dyn Fn() -> Goal<'a, D> + 'a>,
+    name: Option<&'static str>,
+}
 
 impl<'a, D: Domain<'a>> Lazy<'a, D> {
     pub(crate) fn run(self, state: State<'a, D>) -> Option<State<'a, D>>
     where
         D: Domain<'a>,
     {
-        let func = self.0;
-        let goal = func();
+        if let Some(name) = self.name {
+            log::trace!("running relation {}", name);
+        }
+        let goal = (self.func)();
         goal.apply(state)
     }
 }
@@ -43,12 +48,49 @@ where
     D: Domain<'a>,
     F: Fn() -> Goal<'a, D> + 'a,
 {
-    Goal(GoalEnum::Lazy(Lazy(Rc::new(func))))
+    Goal(GoalEnum::Lazy(Lazy {
+        func: Rc::new(func),
+        name: None,
+    }))
+}
+
+/// Create a [lazy](crate::goal::lazy()) [goal](crate::goal::Goal) with a name
+/// for debugging.
+///
+/// The name is shown in the goal's `Debug` output and logged at the `trace`
+/// level each time the goal is evaluated. This is what
+/// [`defrel!`](crate::defrel) uses to make relations traceable.
+///
+/// # Examples
+///
+/// ```
+/// use canrun::{Goal, unify, var};
+/// use canrun::goal::lazy_named;
+/// use canrun::domains::example::I32;
+///
+/// let x = var();
+/// let goal: Goal<I32> = lazy_named("is_one", move || unify(x, 1));
+/// assert!(format!("{:?}", goal).contains("is_one"));
+/// let result: Vec<_> = goal.query(x).collect();
+/// assert_eq!(result, vec![1])
+/// ```
+pub fn lazy_named<'a, D, F>(name: &'static str, func: F) -> Goal<'a, D>
+where
+    D: Domain<'a>,
+    F: Fn() -> Goal<'a, D> + 'a,
+{
+    Goal(GoalEnum::Lazy(Lazy {
+        func: Rc::new(func),
+        name: Some(name),
+    }))
 }
 
 impl<'a, D: Domain<'a>> fmt::Debug for Lazy<'a, D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Lazy ??")
+        match self.name {
+            Some(name) => write!(f, "Lazy({})", name),
+            None => write!(f, "Lazy ??"),
+        }
     }
 }
 
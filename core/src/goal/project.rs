@@ -11,7 +11,11 @@
 mod assert_1;
 mod assert_2;
 mod map_1;
+mod map_1_forward;
+mod map_1_opt;
 mod map_2;
+mod map_2_forward;
+mod map_2_opt;
 mod project_1;
 mod project_2;
 
@@ -22,8 +26,16 @@ pub use assert_2::assert_2;
 #[doc(inline)]
 pub use map_1::map_1;
 #[doc(inline)]
+pub use map_1_forward::map_1_forward;
+#[doc(inline)]
+pub use map_1_opt::map_1_opt;
+#[doc(inline)]
 pub use map_2::map_2;
 #[doc(inline)]
+pub use map_2_forward::map_2_forward;
+#[doc(inline)]
+pub use map_2_opt::map_2_opt;
+#[doc(inline)]
 pub use project_1::project_1;
 #[doc(inline)]
 pub use project_2::project_2;
@@ -0,0 +1,99 @@
+use crate::goal::Goal;
+use crate::state::constraints::{resolve_1, Constraint, ResolveFn, VarWatch};
+use crate::state::State;
+use crate::unify::UnifyIn;
+use crate::value::{IntoVal, Val};
+use crate::DomainType;
+use std::fmt;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Create a [projection goal](super) that derives one resolved value from
+/// the other, but only in one direction.
+///
+/// Unlike [`map_1`](super::map_1), no inverse function is needed. The goal
+/// waits until the first value is resolved and never tries to derive it from
+/// the second. This is useful for lossy derivations such as hashing.
+///
+/// ```
+/// use canrun::{Goal, all, unify, var, map_1_forward};
+/// use canrun::domains::example::I32;
+///
+/// let (x, y) = (var(), var());
+/// let goal: Goal<I32> = all![
+///     unify(-3, x),
+///     map_1_forward(x, y, |x: &i32| x * x),
+/// ];
+/// let result: Vec<_> = goal.query(y).collect();
+/// assert_eq!(result, vec![9])
+/// ```
+pub fn map_1_forward<'a, A, AV, B, BV, D, AtoB>(a: AV, b: BV, a_to_b: AtoB) -> Goal<'a, D>
+where
+    A: Debug + 'a,
+    B: UnifyIn<'a, D> + Debug + 'a,
+    AV: IntoVal<A>,
+    BV: IntoVal<B>,
+    D: DomainType<'a, A> + DomainType<'a, B>,
+    AtoB: Fn(&A) -> B + 'a,
+{
+    Goal::constraint(Map1Forward {
+        a: a.into_val(),
+        b: b.into_val(),
+        a_to_b: Rc::new(a_to_b),
+    })
+}
+
+pub struct Map1Forward<'a, A: Debug, B: Debug> {
+    a: Val<A>,
+    b: Val<B>,
+    a_to_b: Rc<dyn Fn(&A) -> B + 'a>,
+}
+
+impl<'a, A: Debug, B: Debug> Debug for Map1Forward<'a, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Map1Forward {:?} {:?}", self.a, self.b)
+    }
+}
+
+impl<'a, A, B, Dom> Constraint<'a, Dom> for Map1Forward<'a, A, B>
+where
+    A: Debug + 'a,
+    B: UnifyIn<'a, Dom> + Debug + 'a,
+    Dom: DomainType<'a, A> + DomainType<'a, B> + 'a,
+{
+    fn attempt(&self, state: &State<'a, Dom>) -> Result<ResolveFn<'a, Dom>, VarWatch> {
+        let a = resolve_1(&self.a, state)?;
+        let b = self.b.clone();
+        let f = self.a_to_b.clone();
+        Ok(Box::new(move |state| state.unify(&f(&*a).into_val(), &b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_1_forward;
+    use crate::domains::example::I32;
+    use crate::goal::unify::unify;
+    use crate::goal::Goal;
+    use crate::util;
+    use crate::value::var;
+
+    #[test]
+    fn succeeds() {
+        let x = var();
+        let y = var();
+        let goals: Vec<Goal<I32>> = vec![
+            unify(2, x),
+            unify(4, y),
+            map_1_forward(x, y, |x: &i32| x * 2),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(2, 4)]);
+    }
+
+    #[test]
+    fn does_not_invert() {
+        let x = var();
+        let goals: Vec<Goal<I32>> = vec![unify(4, x), map_1_forward(var(), x, |x: &i32| x * 2)];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
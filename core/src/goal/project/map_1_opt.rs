@@ -0,0 +1,121 @@
+use crate::goal::Goal;
+use crate::state::constraints::{Constraint, OneOfTwo, ResolveFn, VarWatch};
+use crate::state::State;
+use crate::unify::UnifyIn;
+use crate::value::{IntoVal, Val};
+use crate::DomainType;
+use std::fmt;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Create a [projection goal](super) that allows fallibly deriving one
+/// resolved value from the other.
+///
+/// This works like [`map_1`](super::map_1), except that the functions return
+/// an [`Option`]. Returning `None` fails the goal instead of producing a
+/// value, which is useful for operations that may overflow or are otherwise
+/// undefined for some inputs.
+///
+/// ```
+/// use canrun::{Goal, all, unify, var, map_1_opt};
+/// use canrun::domains::example::I32;
+///
+/// let (x, y) = (var(), var());
+/// let goal: Goal<I32> = all![
+///     unify(i32::MAX, x),
+///     map_1_opt(x, y, |x: &i32| x.checked_add(1), |y: &i32| y.checked_sub(1)),
+/// ];
+/// let result: Vec<_> = goal.query(y).collect();
+/// assert_eq!(result, vec![])
+/// ```
+pub fn map_1_opt<'a, A, AV, B, BV, D, AtoB, BtoA>(
+    a: AV,
+    b: BV,
+    a_to_b: AtoB,
+    b_to_a: BtoA,
+) -> Goal<'a, D>
+where
+    A: UnifyIn<'a, D> + Debug + 'a,
+    B: UnifyIn<'a, D> + Debug + 'a,
+    AV: IntoVal<A>,
+    BV: IntoVal<B>,
+    D: DomainType<'a, A> + DomainType<'a, B>,
+    AtoB: Fn(&A) -> Option<B> + 'a,
+    BtoA: Fn(&B) -> Option<A> + 'a,
+{
+    Goal::constraint(Map1Opt {
+        a: a.into_val(),
+        b: b.into_val(),
+        a_to_b: Rc::new(a_to_b),
+        b_to_a: Rc::new(b_to_a),
+    })
+}
+
+type MapOptFn<'a, X, Y> = Rc<dyn Fn(&X) -> Option<Y> + 'a>;
+
+pub struct Map1Opt<'a, A: Debug, B: Debug> {
+    a: Val<A>,
+    b: Val<B>,
+    a_to_b: MapOptFn<'a, A, B>,
+    b_to_a: MapOptFn<'a, B, A>,
+}
+
+impl<'a, A: Debug, B: Debug> Debug for Map1Opt<'a, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Map1Opt {:?} {:?}", self.a, self.b)
+    }
+}
+
+impl<'a, A, B, Dom> Constraint<'a, Dom> for Map1Opt<'a, A, B>
+where
+    A: UnifyIn<'a, Dom> + Debug + 'a,
+    B: UnifyIn<'a, Dom> + Debug + 'a,
+    Dom: DomainType<'a, A> + DomainType<'a, B> + 'a,
+{
+    fn attempt(&self, state: &State<'a, Dom>) -> Result<ResolveFn<'a, Dom>, VarWatch> {
+        let resolved = OneOfTwo::resolve(&self.a, &self.b, state)?;
+        match resolved {
+            OneOfTwo::A(a, b) => {
+                let f = self.a_to_b.clone();
+                Ok(Box::new(move |state| state.unify(&f(&*a)?.into_val(), &b)))
+            }
+            OneOfTwo::B(a, b) => {
+                let f = self.b_to_a.clone();
+                Ok(Box::new(move |state| state.unify(&f(&*b)?.into_val(), &a)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_1_opt;
+    use crate::domains::example::I32;
+    use crate::goal::unify::unify;
+    use crate::goal::Goal;
+    use crate::util;
+    use crate::value::var;
+
+    #[test]
+    fn succeeds() {
+        let x = var();
+        let y = var();
+        let goals: Vec<Goal<I32>> = vec![
+            unify(1, x),
+            unify(2, y),
+            map_1_opt(x, y, |x: &i32| x.checked_add(1), |y: &i32| y.checked_sub(1)),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn fails_on_none() {
+        let x = var();
+        let y = var();
+        let goals: Vec<Goal<I32>> = vec![
+            unify(i32::MIN, y),
+            map_1_opt(x, y, |x: &i32| x.checked_add(1), |y: &i32| y.checked_sub(1)),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y), vec![]);
+    }
+}
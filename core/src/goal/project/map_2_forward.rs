@@ -0,0 +1,120 @@
+use crate::goal::Goal;
+use crate::state::constraints::{resolve_2, Constraint, ResolveFn, VarWatch};
+use crate::state::State;
+use crate::unify::UnifyIn;
+use crate::value::{IntoVal, Val};
+use crate::DomainType;
+use std::fmt;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Create a [projection goal](super) that derives a value from two others,
+/// but only in one direction.
+///
+/// Unlike [`map_2`](super::map_2), no inverse functions are needed. The goal
+/// waits until both inputs are resolved and never tries to derive them from
+/// the output.
+///
+/// ```
+/// use canrun::{Goal, all, unify, var, map_2_forward};
+/// use canrun::domains::example::I32;
+///
+/// let (x, y, z) = (var(), var(), var());
+/// let goal: Goal<I32> = all![
+///     unify(7, x),
+///     unify(3, y),
+///     map_2_forward(x, y, z, |x: &i32, y: &i32| x % y),
+/// ];
+/// let result: Vec<_> = goal.query(z).collect();
+/// assert_eq!(result, vec![1])
+/// ```
+pub fn map_2_forward<'a, A, AV, B, BV, C, CV, D, ABtoC>(
+    a: AV,
+    b: BV,
+    c: CV,
+    ab_to_c: ABtoC,
+) -> Goal<'a, D>
+where
+    A: Debug + 'a,
+    AV: IntoVal<A>,
+    B: Debug + 'a,
+    BV: IntoVal<B>,
+    C: UnifyIn<'a, D> + Debug + 'a,
+    CV: IntoVal<C>,
+    D: DomainType<'a, A> + DomainType<'a, B> + DomainType<'a, C>,
+    ABtoC: Fn(&A, &B) -> C + 'a,
+{
+    Goal::constraint(Map2Forward {
+        a: a.into_val(),
+        b: b.into_val(),
+        c: c.into_val(),
+        ab_to_c: Rc::new(ab_to_c),
+    })
+}
+
+type ForwardFn<'a, A, B, C> = Rc<dyn Fn(&A, &B) -> C + 'a>;
+
+pub struct Map2Forward<'a, A: Debug, B: Debug, C: Debug> {
+    a: Val<A>,
+    b: Val<B>,
+    c: Val<C>,
+    ab_to_c: ForwardFn<'a, A, B, C>,
+}
+
+impl<'a, A: Debug, B: Debug, C: Debug> Debug for Map2Forward<'a, A, B, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Map2Forward {:?} {:?} {:?}", self.a, self.b, self.c)
+    }
+}
+
+impl<'a, A, B, C, Dom> Constraint<'a, Dom> for Map2Forward<'a, A, B, C>
+where
+    A: Debug + 'a,
+    B: Debug + 'a,
+    C: UnifyIn<'a, Dom> + Debug + 'a,
+    Dom: DomainType<'a, A> + DomainType<'a, B> + DomainType<'a, C> + 'a,
+{
+    fn attempt(&self, state: &State<'a, Dom>) -> Result<ResolveFn<'a, Dom>, VarWatch> {
+        let (a, b) = resolve_2(&self.a, &self.b, state)?;
+        let c = self.c.clone();
+        let f = self.ab_to_c.clone();
+        Ok(Box::new(move |state| {
+            state.unify(&f(&*a, &*b).into_val(), &c)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_2_forward;
+    use crate::domains::example::I32;
+    use crate::goal::unify::unify;
+    use crate::goal::Goal;
+    use crate::util;
+    use crate::value::var;
+
+    #[test]
+    fn succeeds() {
+        let x = var();
+        let y = var();
+        let z = var();
+        let goals: Vec<Goal<I32>> = vec![
+            unify(7, x),
+            unify(3, y),
+            unify(1, z),
+            map_2_forward(x, y, z, |x: &i32, y: &i32| x % y),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![(7, 3, 1)]);
+    }
+
+    #[test]
+    fn does_not_invert() {
+        let x = var();
+        let z = var();
+        let goals: Vec<Goal<I32>> = vec![
+            unify(1, z),
+            map_2_forward(x, 3, z, |x: &i32, y: &i32| x % y),
+        ];
+        util::assert_permutations_resolve_to(goals, x, vec![]);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::goal::Goal;
+use crate::state::constraints::{Constraint, ResolveFn, TwoOfThree, VarWatch};
+use crate::state::State;
+use crate::unify::UnifyIn;
+use crate::value::{IntoVal, Val};
+use crate::DomainType;
+use std::fmt;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Create a [projection goal](super) that allows fallibly deriving one
+/// resolved value from the other two.
+///
+/// This works like [`map_2`](super::map_2), except that the functions return
+/// an [`Option`]. Returning `None` fails the goal instead of producing a
+/// value.
+///
+/// ```
+/// use canrun::{Goal, all, unify, var, map_2_opt};
+/// use canrun::domains::example::I32;
+///
+/// let (x, y, z) = (var(), var(), var());
+/// let goal: Goal<I32> = all![
+///     unify(1, x),
+///     unify(0, y),
+///     map_2_opt(
+///         x, y, z,
+///         |x: &i32, y: &i32| x.checked_div(*y),
+///         |x: &i32, z: &i32| x.checked_div(*z),
+///         |y: &i32, z: &i32| y.checked_mul(*z),
+///     ),
+/// ];
+/// let result: Vec<_> = goal.query(z).collect();
+/// assert_eq!(result, vec![])
+/// ```
+pub fn map_2_opt<'a, A, AV, B, BV, C, CV, D, ABtoC, ACtoB, BCtoA>(
+    a: AV,
+    b: BV,
+    c: CV,
+    ab_to_c: ABtoC,
+    ac_to_b: ACtoB,
+    bc_to_a: BCtoA,
+) -> Goal<'a, D>
+where
+    A: UnifyIn<'a, D> + Debug + 'a,
+    AV: IntoVal<A>,
+    B: UnifyIn<'a, D> + Debug + 'a,
+    BV: IntoVal<B>,
+    C: UnifyIn<'a, D> + Debug + 'a,
+    CV: IntoVal<C>,
+    D: DomainType<'a, A> + DomainType<'a, B> + DomainType<'a, C>,
+    ABtoC: Fn(&A, &B) -> Option<C> + 'a,
+    ACtoB: Fn(&A, &C) -> Option<B> + 'a,
+    BCtoA: Fn(&B, &C) -> Option<A> + 'a,
+{
+    Goal::constraint(Map2Opt {
+        a: a.into_val(),
+        b: b.into_val(),
+        c: c.into_val(),
+        ab_to_c: Rc::new(ab_to_c),
+        ac_to_b: Rc::new(ac_to_b),
+        bc_to_a: Rc::new(bc_to_a),
+    })
+}
+
+type MapOptFn<'a, X, Y, Z> = Rc<dyn Fn(&X, &Y) -> Option<Z> + 'a>;
+
+pub struct Map2Opt<'a, A: Debug, B: Debug, C: Debug> {
+    a: Val<A>,
+    b: Val<B>,
+    c: Val<C>,
+    ab_to_c: MapOptFn<'a, A, B, C>,
+    ac_to_b: MapOptFn<'a, A, C, B>,
+    bc_to_a: MapOptFn<'a, B, C, A>,
+}
+
+impl<'a, A: Debug, B: Debug, C: Debug> Debug for Map2Opt<'a, A, B, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Map2Opt {:?} {:?} {:?}", self.a, self.b, self.c)
+    }
+}
+
+impl<'a, A, B, C, Dom> Constraint<'a, Dom> for Map2Opt<'a, A, B, C>
+where
+    A: UnifyIn<'a, Dom> + Debug + 'a,
+    B: UnifyIn<'a, Dom> + Debug + 'a,
+    C: UnifyIn<'a, Dom> + Debug + 'a,
+    Dom: DomainType<'a, A> + DomainType<'a, B> + DomainType<'a, C> + 'a,
+{
+    fn attempt(&self, state: &State<'a, Dom>) -> Result<ResolveFn<'a, Dom>, VarWatch> {
+        use TwoOfThree::*;
+        match TwoOfThree::resolve(&self.a, &self.b, &self.c, state)? {
+            AB(a, b, c) => {
+                let f = self.ab_to_c.clone();
+                Ok(Box::new(move |state| {
+                    state.unify(&f(&*a, &*b)?.into_val(), &c)
+                }))
+            }
+            BC(a, b, c) => {
+                let f = self.bc_to_a.clone();
+                Ok(Box::new(move |state| {
+                    state.unify(&f(&*b, &*c)?.into_val(), &a)
+                }))
+            }
+            AC(a, b, c) => {
+                let f = self.ac_to_b.clone();
+                Ok(Box::new(move |state| {
+                    state.unify(&f(&*a, &*c)?.into_val(), &b)
+                }))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_2_opt;
+    use crate::domains::example::I32;
+    use crate::goal::unify::unify;
+    use crate::goal::Goal;
+    use crate::util;
+    use crate::value::var;
+
+    #[test]
+    fn succeeds() {
+        let x = var();
+        let y = var();
+        let z = var();
+        let goals: Vec<Goal<I32>> = vec![
+            unify(1, x),
+            unify(2, y),
+            unify(3, z),
+            map_2_opt(
+                x,
+                y,
+                z,
+                |x: &i32, y: &i32| x.checked_add(*y),
+                |x: &i32, z: &i32| z.checked_sub(*x),
+                |y: &i32, z: &i32| z.checked_sub(*y),
+            ),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, y, z), vec![(1, 2, 3)]);
+    }
+
+    #[test]
+    fn fails_on_none() {
+        let x = var();
+        let z = var();
+        let goals: Vec<Goal<I32>> = vec![
+            unify(i32::MAX, x),
+            map_2_opt(
+                x,
+                1,
+                z,
+                |x: &i32, y: &i32| x.checked_add(*y),
+                |x: &i32, z: &i32| z.checked_sub(*x),
+                |y: &i32, z: &i32| z.checked_sub(*y),
+            ),
+        ];
+        util::assert_permutations_resolve_to(goals, (x, z), vec![]);
+    }
+}
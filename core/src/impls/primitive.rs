@@ -1,4 +1,6 @@
 use crate::{Domain, DomainType, ReifyIn, ResolvedState, State, UnifyIn};
+#[cfg(feature = "rational")]
+use num_rational::{Rational32, Rational64};
 use std::rc::Rc;
 
 macro_rules! impl_unify_eq {
@@ -45,7 +47,11 @@ macro_rules! impl_reify_clone {
 
 impl_unify_eq!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize, f32, f64);
 impl_unify_eq!(String, &'static str, bool, char);
+#[cfg(feature = "rational")]
+impl_unify_eq!(Rational32, Rational64);
 
 impl_reify_copy!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize, f32, f64);
 impl_reify_clone!(String);
 impl_reify_copy!(&'static str, bool, char);
+#[cfg(feature = "rational")]
+impl_reify_copy!(Rational32, Rational64);
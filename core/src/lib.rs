@@ -34,7 +34,12 @@ pub mod value;
 #[doc(inline)]
 pub use domains::{Domain, DomainType};
 #[doc(inline)]
-pub use goal::project::{assert_1, assert_2, map_1, map_2, project_1, project_2};
+pub use goal::defrel;
+#[doc(inline)]
+pub use goal::project::{
+    assert_1, assert_2, map_1, map_1_forward, map_1_opt, map_2, map_2_forward, map_2_opt,
+    project_1, project_2,
+};
 #[doc(inline)]
 pub use goal::{both, custom, either, lazy, unify, Goal};
 #[doc(inline)]
@@ -51,12 +56,18 @@ pub use impls::tuples::*;
 #[doc(inline)]
 pub use domains::domain;
 
+pub use canrun_codegen::{ReifyIn, UnifyIn};
+
 pub mod util;
 
 #[cfg(test)]
 mod tests {
     mod test_constrain;
+    mod test_defrel;
+    mod test_derive;
+    mod test_domain_extension;
     mod test_fork;
+    mod test_generic_domain;
     mod test_unify;
 }
 
@@ -24,13 +24,14 @@ use super::util::multikeymultivaluemap::MKMVMap;
 use crate::domains::{Domain, DomainType};
 use crate::unify::UnifyIn;
 use crate::value::{
-    LVarId, Val,
+    LVar, LVarId, Val,
     Val::{Resolved, Var},
 };
 #[doc(hidden)]
 pub use constraints::Constraint;
 pub use iter_resolved::{IterResolved, ResolvedStateIter};
 pub use resolved::ResolvedState;
+use std::any::{Any, TypeId};
 use std::fmt::Debug;
 use std::iter::once;
 use std::rc::Rc;
@@ -69,6 +70,7 @@ pub struct State<'a, D: Domain<'a> + 'a> {
     domain: D,
     constraints: ConstraintFns<'a, D>,
     forks: im_rc::Vector<Rc<dyn Fork<'a, D> + 'a>>,
+    extensions: im_rc::HashMap<TypeId, Rc<dyn Any>>,
 }
 
 impl<'a, D: Domain<'a> + 'a> State<'a, D> {
@@ -93,6 +95,7 @@ impl<'a, D: Domain<'a> + 'a> State<'a, D> {
             domain: D::new(),
             constraints: MKMVMap::new(),
             forks: im_rc::Vector::new(),
+            extensions: im_rc::HashMap::new(),
         }
     }
 
@@ -211,17 +214,63 @@ impl<'a, D: Domain<'a> + 'a> State<'a, D> {
                 self.domain.update(key, value);
 
                 // check constraints matching newly assigned lvar
-                if let Some(constraints) = self.constraints.extract(&key.id) {
-                    constraints
-                        .into_iter()
-                        .try_fold(self, |state, func| state.constrain(func))
-                } else {
-                    Some(self)
-                }
+                self.wake(key)
             }
         }
     }
 
+    /// Reevaluate the [constraints](State::constrain) watching an [`LVar`].
+    ///
+    /// This happens automatically when a variable is bound. Lower level code
+    /// that tracks extra information about a variable (see
+    /// [`.extension()`](State::extension())) can use this to let other
+    /// constraints know when that information changes.
+    pub fn wake<T>(mut self, var: LVar<T>) -> Option<Self> {
+        if let Some(constraints) = self.constraints.extract(&var.id) {
+            constraints
+                .into_iter()
+                .try_fold(self, |state, func| state.constrain(func))
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Get a previously stored extension value by type.
+    ///
+    /// Extensions allow higher level goals to keep their own bookkeeping
+    /// (such as the known bounds of a variable) alongside the bindings. Only
+    /// one value of each type can be stored in a state.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun::State;
+    /// use canrun::domains::example::I32;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Visits(usize);
+    ///
+    /// let state: State<I32> = State::new();
+    /// assert_eq!(state.extension::<Visits>(), None);
+    ///
+    /// let state = state.set_extension(Visits(1));
+    /// assert_eq!(state.extension::<Visits>(), Some(&Visits(1)));
+    /// ```
+    pub fn extension<E: Any>(&self) -> Option<&E> {
+        self.extensions.get(&TypeId::of::<E>())?.downcast_ref()
+    }
+
+    /// Store an extension value, replacing any existing value of the same
+    /// type.
+    ///
+    /// Cloned states share the stored value, so each update means storing a
+    /// new copy. Persistent collections such as those in the `im-rc` crate
+    /// keep that cheap.
+    pub fn set_extension<E: Any>(mut self, extension: E) -> Self {
+        self.extensions
+            .insert(TypeId::of::<E>(), Rc::new(extension));
+        self
+    }
+
     /// Add a constraint to the store that can be reevaluated as variables are
     /// resolved.
     ///
@@ -101,6 +101,26 @@ impl VarWatch {
     pub fn two<A, B>(a: LVar<A>, b: LVar<B>) -> Self {
         VarWatch(vec![a.id, b.id])
     }
+
+    /// Watch any number of [`LVar`]s for changes in a [`Constraint`].
+    ///
+    /// The constraint will be reattempted when any one of them is bound. Note
+    /// that a constraint watching no variables will never be attempted again.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun::state::constraints::VarWatch;
+    /// use canrun::{var, LVar};
+    ///
+    /// let vars: Vec<LVar<i32>> = vec![var(), var(), var()];
+    /// let watch = VarWatch::many(vars);
+    /// ```
+    pub fn many<A, I>(vars: I) -> Self
+    where
+        I: IntoIterator<Item = LVar<A>>,
+    {
+        VarWatch(vars.into_iter().map(|var| var.id).collect())
+    }
 }
 
 /// Resolve one [`Val`] or return an [`Err(VarWatch)`](VarWatch) in a
@@ -14,7 +14,12 @@ pub struct ResolvedState<D> {
 }
 
 impl<'a, D: Domain<'a> + 'a> ResolvedState<D> {
-    pub(crate) fn resolve_val<'r, T>(&'r self, val: &'r Val<T>) -> &'r Val<T>
+    /// Recursively resolve a [`Val`](crate::value::Val) as far as the variable
+    /// bindings in this state allow.
+    ///
+    /// This is mostly useful for implementing [`ReifyIn`] on recursive
+    /// structures without reifying every level separately.
+    pub fn resolve_val<'r, T>(&'r self, val: &'r Val<T>) -> &'r Val<T>
     where
         T: Debug,
         D: DomainType<'a, T>,
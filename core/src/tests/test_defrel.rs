@@ -0,0 +1,39 @@
+use crate as canrun;
+use crate::{all, any, defrel, domain, fresh, unify, util, var, Goal};
+
+domain! {
+    Family { &'static str }
+}
+
+defrel! {
+    fn parent(p: &'static str, c: &'static str) {
+        any![
+            all![unify(p.clone(), "alice"), unify(c.clone(), "bob")],
+            all![unify(p.clone(), "bob"), unify(c.clone(), "carol")],
+            all![unify(p, "carol"), unify(c, "dave")],
+        ]
+    }
+
+    fn ancestor(a: &'static str, d: &'static str) -> Goal<'a, Family> {
+        any![
+            parent(a.clone(), d.clone()),
+            fresh!(|p| all![parent(a.clone(), p), ancestor(p, d.clone())]),
+        ]
+    }
+}
+
+#[test]
+fn recursive_relations() {
+    let x = var();
+    let goals: Vec<Goal<Family>> = vec![ancestor("alice", x)];
+    util::assert_permutations_resolve_to(goals, x, vec!["bob", "carol", "dave"]);
+
+    let goals: Vec<Goal<Family>> = vec![ancestor(x, "dave")];
+    util::assert_permutations_resolve_to(goals, x, vec!["carol", "bob", "alice"]);
+}
+
+#[test]
+fn named_in_debug_output() {
+    let goal: Goal<Family> = ancestor("alice", var());
+    assert!(format!("{:?}", goal).contains("ancestor"));
+}
@@ -0,0 +1,154 @@
+use crate as canrun;
+use crate::{domain, unify, util, val, var, Goal, ReifyIn, UnifyIn, Val};
+
+#[derive(Debug, UnifyIn, ReifyIn)]
+#[reified(Shape)]
+enum LShape {
+    Circle(Val<f64>),
+    Rect(Val<f64>, Val<f64>),
+    Empty,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+    Empty,
+}
+
+#[derive(Debug, UnifyIn, ReifyIn)]
+#[reified(Point)]
+struct LPoint {
+    x: Val<i32>,
+    y: Val<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, UnifyIn, ReifyIn)]
+#[reified(Expr)]
+enum LExpr {
+    Num(Val<i32>),
+    Add(Val<LExpr>, Val<LExpr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Num(i32),
+    Add(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, UnifyIn, ReifyIn)]
+#[reified(Option<T>)]
+enum LOption<T: std::fmt::Debug> {
+    Some(Val<T>),
+    None,
+}
+
+domain! {
+    Terms {
+        i32,
+        f64,
+        LShape,
+        LPoint,
+        LExpr,
+        LOption<i32>,
+    }
+}
+
+#[test]
+fn same_variant_unifies_fields() {
+    let (s, w) = (var(), var());
+    let goals: Vec<Goal<Terms>> = vec![
+        unify(s, LShape::Rect(val!(w), val!(3.0))),
+        unify(s, LShape::Rect(val!(2.0), val!(3.0))),
+    ];
+    util::assert_permutations_resolve_to(goals, (s, w), vec![(Shape::Rect(2.0, 3.0), 2.0)]);
+}
+
+#[test]
+fn different_variants_fail() {
+    let s = var();
+    let goals: Vec<Goal<Terms>> = vec![
+        unify(s, LShape::Circle(val!(var()))),
+        unify(s, LShape::Rect(val!(2.0), val!(3.0))),
+    ];
+    util::assert_permutations_resolve_to(goals, s, vec![]);
+}
+
+#[test]
+fn unit_variants() {
+    let s = var();
+    let goals: Vec<Goal<Terms>> = vec![unify(s, LShape::Empty), unify(s, LShape::Empty)];
+    util::assert_permutations_resolve_to(goals, s, vec![Shape::Empty]);
+}
+
+#[test]
+fn mismatched_fields_fail() {
+    let s = var();
+    let goals: Vec<Goal<Terms>> = vec![
+        unify(s, LShape::Circle(val!(1.0))),
+        unify(s, LShape::Circle(val!(2.0))),
+    ];
+    util::assert_permutations_resolve_to(goals, s, vec![]);
+}
+
+#[test]
+fn structs_unify_named_fields() {
+    let (p, x) = (var(), var());
+    let goals: Vec<Goal<Terms>> = vec![
+        unify(
+            p,
+            LPoint {
+                x: val!(x),
+                y: val!(2),
+            },
+        ),
+        unify(
+            p,
+            LPoint {
+                x: val!(1),
+                y: val!(2),
+            },
+        ),
+    ];
+    util::assert_permutations_resolve_to(goals, (p, x), vec![(Point { x: 1, y: 2 }, 1)]);
+}
+
+#[test]
+fn recursive_enums() {
+    let (e, x) = (var(), var());
+    let nested = |n| val!(LExpr::Num(val!(n)));
+    let goals: Vec<Goal<Terms>> = vec![
+        unify(e, LExpr::Add(nested(1), val!(x))),
+        unify(
+            e,
+            LExpr::Add(nested(1), val!(LExpr::Add(nested(2), nested(3)))),
+        ),
+    ];
+    util::assert_permutations_resolve_to(
+        goals,
+        e,
+        vec![Expr::Add(
+            Box::new(Expr::Num(1)),
+            Box::new(Expr::Add(Box::new(Expr::Num(2)), Box::new(Expr::Num(3)))),
+        )],
+    );
+}
+
+#[test]
+fn generic_enums() {
+    let (o, x) = (var(), var());
+    let goals: Vec<Goal<Terms>> = vec![
+        unify(o, LOption::Some(val!(x))),
+        unify(o, LOption::Some(val!(1))),
+    ];
+    util::assert_permutations_resolve_to(goals, (o, x), vec![(Some(1), 1)]);
+
+    let goals: Vec<Goal<Terms>> = vec![unify(o, LOption::Some(val!(x))), unify(o, LOption::None)];
+    util::assert_permutations_resolve_to(goals, o, vec![]);
+}
@@ -0,0 +1,113 @@
+use crate as canrun;
+use crate::{
+    domain, either, ltup, unify, util, var, DomainType, Goal, IntoVal, LVar, ReifyIn, UnifyIn, Val,
+};
+use std::fmt::Debug;
+
+domain! {
+    Base {
+        i32,
+        (Val<i32>, Val<i32>),
+    }
+}
+
+#[derive(Debug, UnifyIn, ReifyIn)]
+#[reified(Tagged)]
+struct LTagged {
+    id: Val<i32>,
+    tag: Val<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Tagged {
+    id: i32,
+    tag: String,
+}
+
+domain! {
+    Extended: Base {
+        String,
+        LTagged,
+    }
+}
+
+domain! {
+    Further: Extended {
+        bool,
+    }
+}
+
+/// Written against the base types without knowing about any extensions.
+fn one_of_two<'a, T, D>(x: impl IntoVal<T>, pair: impl IntoVal<(Val<T>, Val<T>)>) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, T> + DomainType<'a, (Val<T>, Val<T>)>,
+{
+    let (x, a, b): (Val<T>, LVar<T>, LVar<T>) = (x.into_val(), var(), var());
+    crate::all![
+        unify(pair, ltup!(a, b)),
+        either(unify::<T, _, _, D>(x.clone(), a), unify::<T, _, _, D>(x, b))
+    ]
+}
+
+#[test]
+fn base_goals_run_in_extended_domain() {
+    let x = var();
+    let goals: Vec<Goal<Extended>> = vec![one_of_two(x, ltup!(1, 2))];
+    util::assert_permutations_resolve_to(goals, x, vec![1, 2]);
+}
+
+#[test]
+fn base_and_extended_types_mix() {
+    let (x, t) = (var(), var());
+    let goals: Vec<Goal<Extended>> = vec![
+        one_of_two(x, ltup!(1, 2)),
+        unify(
+            t,
+            LTagged {
+                id: x.into_val(),
+                tag: String::from("one").into_val(),
+            },
+        ),
+        unify(
+            t,
+            LTagged {
+                id: 1.into_val(),
+                tag: var().into_val(),
+            },
+        ),
+    ];
+    util::assert_permutations_resolve_to(
+        goals,
+        t,
+        vec![Tagged {
+            id: 1,
+            tag: String::from("one"),
+        }],
+    );
+}
+
+#[test]
+fn base_values_fail_to_unify() {
+    let x = var();
+    let goals: Vec<Goal<Extended>> = vec![unify(x, 1), unify(x, 2)];
+    util::assert_permutations_resolve_to(goals, x, vec![]);
+}
+
+#[test]
+fn extensions_chain() {
+    let (x, y, z) = (var(), var(), var());
+    let goals: Vec<Goal<Further>> = vec![
+        one_of_two(x, ltup!(1, 2)),
+        unify(y, String::from("two")),
+        unify(z, true),
+    ];
+    util::assert_permutations_resolve_to(
+        goals,
+        (x, y, z),
+        vec![
+            (1, String::from("two"), true),
+            (2, String::from("two"), true),
+        ],
+    );
+}
@@ -0,0 +1,37 @@
+use crate as canrun;
+use crate::{domain, ltup, unify, util, var, DomainType, Goal, IntoVal, UnifyIn, Val};
+use std::fmt::Debug;
+
+domain! {
+    Pairs<T> where T: PartialEq {
+        T,
+        (Val<T>, Val<T>),
+    }
+}
+
+/// A "relation pack" that only knows about the element type it is handed.
+fn swapped<'a, T, D>(
+    a: impl IntoVal<(Val<T>, Val<T>)>,
+    b: impl IntoVal<(Val<T>, Val<T>)>,
+) -> Goal<'a, D>
+where
+    T: UnifyIn<'a, D> + Debug + 'a,
+    D: DomainType<'a, T> + DomainType<'a, (Val<T>, Val<T>)>,
+{
+    let (x, y) = (var(), var());
+    crate::all![unify(a, ltup!(x, y)), unify(b, ltup!(y, x))]
+}
+
+#[test]
+fn generic_over_element_type() {
+    let (a, b) = (var(), var());
+    let goals: Vec<Goal<Pairs<i32>>> = vec![unify(a, ltup!(1, 2)), swapped(a, b)];
+    util::assert_permutations_resolve_to(goals, b, vec![(2, 1)]);
+
+    let (a, b) = (var(), var());
+    let goals: Vec<Goal<Pairs<String>>> = vec![
+        unify(a, ltup!(String::from("l"), String::from("r"))),
+        swapped(a, b),
+    ];
+    util::assert_permutations_resolve_to(goals, b, vec![("r".to_string(), "l".to_string())]);
+}
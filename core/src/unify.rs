@@ -37,9 +37,42 @@ use std::rc::Rc;
 /// # Implementation
 ///
 /// Default implementations are provided for most primitive types and some
-/// collections. You can also implement it for your own types.
+/// collections. Structs and enums whose fields are all [`Val`](crate::Val)s
+/// can derive it. Enum values only unify when they are the same variant, in
+/// which case their fields are unified with each other.
+/// ```
+/// use canrun::{var, val, unify, Goal, Val, UnifyIn, ReifyIn};
+///
+/// #[derive(Debug, UnifyIn, ReifyIn)]
+/// #[reified(Shape)]
+/// enum LShape {
+///     Circle(Val<f64>),
+///     Rect(Val<f64>, Val<f64>),
+/// }
+///
+/// // The plain type each `LShape` reifies into.
+/// #[derive(Debug, PartialEq)]
+/// enum Shape {
+///     Circle(f64),
+///     Rect(f64, f64),
+/// }
+///
+/// canrun::domain! {
+///     Shapes { f64, LShape }
+/// }
+///
+/// # fn main() {
+/// let (shape, width) = (var(), var());
+/// let goal: Goal<Shapes> = canrun::all![
+///     unify(shape, LShape::Rect(val!(2.0), val!(3.0))),
+///     unify(shape, LShape::Rect(val!(width), val!(3.0))),
+/// ];
+/// let results: Vec<_> = goal.query((shape, width)).collect();
+/// assert_eq!(results, vec![(Shape::Rect(2.0, 3.0), 2.0)]);
+/// # }
+/// ```
 ///
-/// TODO: Create a derive macro
+/// You can also implement it by hand.
 /// ```
 /// use canrun::{State, DomainType, UnifyIn};
 /// use std::rc::Rc;
@@ -7,15 +7,18 @@
 mod into_val;
 mod lvar;
 mod reify_in;
+mod var_scope;
 
 pub use into_val::IntoVal;
 pub(super) use lvar::LVarId;
 pub use lvar::{var, LVar};
 pub use reify_in::ReifyIn;
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
+pub use var_scope::VarScope;
 
 /// The possible states a value can be in.
 pub enum Val<T: Debug + ?Sized> {
@@ -130,6 +133,30 @@ impl<T: PartialEq + Debug> PartialEq for Val<T> {
 }
 impl<T: Eq + Debug> Eq for Val<T> {}
 
+/// Resolved values are ordered before variables, which are ordered by
+/// creation. This allows `Val`s to be used as keys in ordered collections.
+impl<T: PartialOrd + Debug> PartialOrd for Val<T> {
+    fn partial_cmp(&self, other: &Val<T>) -> Option<Ordering> {
+        match (self, other) {
+            (Resolved(s), Resolved(other)) => s.partial_cmp(other),
+            (Var(s), Var(other)) => s.partial_cmp(other),
+            (Resolved(_), Var(_)) => Some(Ordering::Less),
+            (Var(_), Resolved(_)) => Some(Ordering::Greater),
+        }
+    }
+}
+
+impl<T: Ord + Debug> Ord for Val<T> {
+    fn cmp(&self, other: &Val<T>) -> Ordering {
+        match (self, other) {
+            (Resolved(s), Resolved(other)) => s.cmp(other),
+            (Var(s), Var(other)) => s.cmp(other),
+            (Resolved(_), Var(_)) => Ordering::Less,
+            (Var(_), Resolved(_)) => Ordering::Greater,
+        }
+    }
+}
+
 impl<T: Hash + Debug> Hash for Val<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match self {
@@ -1,13 +1,25 @@
+use super::var_scope::{fmt_id, next_scoped_id};
+use std::cmp;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-pub(in super::super) type LVarId = usize;
+/// Identifies an [`LVar`]. Globally allocated ids have a `scope` of `0`, and
+/// each [`VarScope`](crate::value::VarScope) numbers its own vars under a
+/// distinct `scope` tag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(in super::super) struct LVarId {
+    pub(super) scope: usize,
+    pub(super) local: usize,
+}
 
 fn get_id() -> LVarId {
     static COUNTER: AtomicUsize = AtomicUsize::new(1);
-    COUNTER.fetch_add(1, Ordering::Relaxed)
+    next_scoped_id().unwrap_or_else(|| LVarId {
+        scope: 0,
+        local: COUNTER.fetch_add(1, Ordering::Relaxed),
+    })
 }
 
 /// A logical variable that represents a potential value of type `T`.
@@ -22,7 +34,8 @@ fn get_id() -> LVarId {
 /// The identity of each `LVar` is tracked using an internal id. While this id
 /// is visible through the `Debug` implementation, it should only be used for
 /// debugging purposes as no guarantees are made about the type or generation of
-/// the id value.
+/// the id value. A [`VarScope`](crate::value::VarScope) can be used to make
+/// the ids reproducible.
 #[derive(Default)]
 pub struct LVar<T: ?Sized> {
     pub(in super::super) id: LVarId,
@@ -51,6 +64,19 @@ impl<T> PartialEq for LVar<T> {
 }
 impl<T> Eq for LVar<T> {}
 
+impl<T> PartialOrd for LVar<T> {
+    fn partial_cmp(&self, other: &LVar<T>) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `LVar`s are ordered by their id, which roughly follows creation order.
+impl<T> Ord for LVar<T> {
+    fn cmp(&self, other: &LVar<T>) -> cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
 impl<T> LVar<T> {
     /// Create a new [logical var](LVar).
     ///
@@ -111,9 +137,11 @@ impl<T> Hash for LVar<T> {
 
 impl<T> fmt::Debug for LVar<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LVar(")?;
+        fmt_id(self.id, f)?;
         match self.label {
-            Some(label) => write!(f, "LVar({}/{})", self.id, label),
-            None => write!(f, "LVar({})", self.id),
+            Some(label) => write!(f, "/{})", label),
+            None => write!(f, ")"),
         }
     }
 }
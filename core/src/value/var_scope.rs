@@ -0,0 +1,264 @@
+use super::lvar::LVarId;
+use super::LVar;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug)]
+struct Counter {
+    scope: usize,
+    next: Cell<usize>,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Rc<Counter>>> = const { RefCell::new(None) };
+    static RENUMBERED: RefCell<Option<HashMap<LVarId, usize>>> = const { RefCell::new(None) };
+}
+
+/// Take the next id from the active [`VarScope`], if there is one.
+pub(super) fn next_scoped_id() -> Option<LVarId> {
+    ACTIVE.with(|active| {
+        active.borrow().as_ref().map(|counter| {
+            let local = counter.next.get();
+            counter.next.set(local + 1);
+            LVarId {
+                scope: counter.scope,
+                local,
+            }
+        })
+    })
+}
+
+/// Write the id shown in debug output. Inside of [`VarScope::renumber`] this
+/// is the order in which the var first appeared. Otherwise scoped ids are
+/// marked with an `@` so they can't be mistaken for global ones.
+pub(super) fn fmt_id(id: LVarId, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let renumbered = RENUMBERED.with(|renumbered| {
+        renumbered.borrow_mut().as_mut().map(|seen| {
+            let next = seen.len() + 1;
+            *seen.entry(id).or_insert(next)
+        })
+    });
+    match renumbered {
+        Some(number) => write!(f, "{}", number),
+        None if id.scope == 0 => write!(f, "{}", id.local),
+        None => write!(f, "@{}", id.local),
+    }
+}
+
+/// Allocate [`LVar`] ids locally instead of from the process wide counter.
+///
+/// By default every `LVar` gets its id from a global counter, so the ids (and
+/// any `Debug` output containing them) depend on everything else that has run
+/// in the process. Variables created while a scope is
+/// [entered](VarScope::enter()) are numbered starting from `1`, so running the
+/// same query in a fresh scope always prints the same way. Scoped variables
+/// print with an `@` before their number, as in `LVar(@1)`.
+///
+/// Each scope also tags its ids, so they never collide with globally
+/// allocated ids or with ids from any other scope, even though they print
+/// the same. To make output reproducible without creating the variables in a
+/// scope, format it inside of [`VarScope::renumber`] instead.
+///
+/// # Example:
+/// ```
+/// use canrun::{unify, var, Goal, LVar};
+/// use canrun::value::VarScope;
+/// use canrun::domains::example::I32;
+///
+/// let debug_goal = || {
+///     VarScope::new().enter(|| {
+///         let x: LVar<i32> = var();
+///         let goal: Goal<I32> = unify(x, 1);
+///         format!("{:?}", goal)
+///     })
+/// };
+/// assert_eq!(debug_goal(), debug_goal());
+/// ```
+#[derive(Clone, Debug)]
+pub struct VarScope {
+    counter: Rc<Counter>,
+}
+
+impl VarScope {
+    /// Create a new scope that starts numbering variables from `1`.
+    pub fn new() -> Self {
+        // Tag `0` is used for globally allocated ids.
+        static SCOPES: AtomicUsize = AtomicUsize::new(1);
+        VarScope {
+            counter: Rc::new(Counter {
+                scope: SCOPES.fetch_add(1, Ordering::Relaxed),
+                next: Cell::new(1),
+            }),
+        }
+    }
+
+    /// Run a function with this scope active, so that any [`LVar`] created on
+    /// this thread (including inside of [lazy goals](crate::goal::lazy())
+    /// evaluated during the call) takes its id from the scope.
+    ///
+    /// A scope can be entered more than once, and the numbering continues
+    /// where it left off. Note that query iterators are lazy, so they should be
+    /// consumed inside the call for their variables to be numbered by the
+    /// scope.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun::{var, LVar};
+    /// use canrun::value::VarScope;
+    ///
+    /// let scope = VarScope::new();
+    /// let x: LVar<i32> = scope.enter(var);
+    /// let y: LVar<i32> = scope.enter(var);
+    /// assert_eq!(format!("{:?} {:?}", x, y), "LVar(@1) LVar(@2)");
+    /// ```
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        // Restore the previous scope even if `f` panics.
+        struct Restore(Option<Rc<Counter>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                ACTIVE.with(|active| *active.borrow_mut() = previous);
+            }
+        }
+        let previous = ACTIVE.with(|active| active.replace(Some(self.counter.clone())));
+        let _restore = Restore(previous);
+        f()
+    }
+
+    /// Create a new [`LVar`] with an id from this scope.
+    pub fn var<T>(&self) -> LVar<T> {
+        self.enter(LVar::new)
+    }
+
+    /// Run a function that formats debug output, numbering every [`LVar`] in
+    /// the order it first appears instead of by its id.
+    ///
+    /// This works for variables from anywhere, whether they were created in a
+    /// scope or not. Calls can be nested, in which case the numbering of the
+    /// outermost call is shared. Reified query results never contain unbound
+    /// variables, so this is only needed for things like goals, states and
+    /// traces.
+    ///
+    /// # Example:
+    /// ```
+    /// use canrun::{unify, var, Goal, LVar};
+    /// use canrun::value::VarScope;
+    /// use canrun::domains::example::I32;
+    ///
+    /// let debug_goal = || {
+    ///     let (x, y): (LVar<i32>, LVar<i32>) = (var(), var());
+    ///     let goal: Goal<I32> = unify(y, x);
+    ///     VarScope::renumber(|| format!("{:?} {:?}", goal, x))
+    /// };
+    /// assert_eq!(debug_goal(), debug_goal());
+    /// assert!(debug_goal().ends_with("LVar(2)"));
+    /// ```
+    pub fn renumber<R>(f: impl FnOnce() -> R) -> R {
+        struct Restore(bool);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                if self.0 {
+                    RENUMBERED.with(|renumbered| *renumbered.borrow_mut() = None);
+                }
+            }
+        }
+        let outermost = RENUMBERED.with(|renumbered| {
+            let mut renumbered = renumbered.borrow_mut();
+            let outermost = renumbered.is_none();
+            if outermost {
+                *renumbered = Some(HashMap::new());
+            }
+            outermost
+        });
+        let _restore = Restore(outermost);
+        f()
+    }
+}
+
+impl Default for VarScope {
+    fn default() -> Self {
+        VarScope::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VarScope;
+    use crate::domains::example::I32;
+    use crate::{either, lazy, unify, var, Goal, LVar};
+
+    #[test]
+    fn numbers_from_one() {
+        let scope = VarScope::new();
+        let (x, y): (LVar<i32>, LVar<i32>) = (scope.var(), scope.var());
+        assert_eq!(format!("{:?} {:?}", x, y), "LVar(@1) LVar(@2)");
+    }
+
+    #[test]
+    fn does_not_collide_with_global_ids() {
+        let scoped: LVar<i32> = VarScope::new().var();
+        let global: LVar<i32> = var();
+        assert_ne!(scoped, global);
+        assert_ne!(format!("{:?}", scoped), format!("{:?}", global));
+    }
+
+    #[test]
+    fn separate_scopes_do_not_collide() {
+        let a: LVar<i32> = VarScope::new().var();
+        let b: LVar<i32> = VarScope::new().var();
+        assert_eq!(format!("{:?}", a), format!("{:?}", b));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn nested_scopes_restore_the_outer_scope() {
+        let outer = VarScope::new();
+        let (a, b, c) = outer.enter(|| {
+            let a: LVar<i32> = var();
+            let b: LVar<i32> = VarScope::new().enter(var);
+            let c: LVar<i32> = var();
+            (a, b, c)
+        });
+        assert_eq!(
+            format!("{:?} {:?} {:?}", a, b, c),
+            "LVar(@1) LVar(@1) LVar(@2)"
+        );
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn renumbers_by_first_appearance() {
+        let (x, y): (LVar<i32>, LVar<i32>) = (var(), VarScope::new().var());
+        let labeled: LVar<i32> = LVar::labeled("z");
+        let debug = VarScope::renumber(|| {
+            let outer = format!("{:?} {:?}", y, labeled);
+            let inner = VarScope::renumber(|| format!("{:?} {:?}", x, y));
+            format!("{} {}", outer, inner)
+        });
+        assert_eq!(debug, "LVar(1) LVar(2/z) LVar(3) LVar(1)");
+        assert_eq!(format!("{:?}", y), "LVar(@1)");
+    }
+
+    #[test]
+    fn identical_queries_print_identically() {
+        let run = || {
+            VarScope::new().enter(|| {
+                let x = var();
+                let goal: Goal<I32> = either(
+                    unify(x, 1),
+                    lazy(move || {
+                        let y = var();
+                        unify(x, y)
+                    }),
+                );
+                let debug = format!("{:?}", goal);
+                let results: Vec<_> = goal.query(x).collect();
+                (debug, results)
+            })
+        };
+        assert_eq!(run(), run());
+    }
+}